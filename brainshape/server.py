@@ -1455,7 +1455,11 @@ if __name__ == "__main__":
     parser.add_argument("--host", default="127.0.0.1")
     parser.add_argument("--port", type=int, default=52836)
     parser.add_argument("--reload", action="store_true")
+    parser.add_argument(
+        "--log-level", default="info", choices=["critical", "error", "warning", "info", "debug"]
+    )
     args = parser.parse_args()
+    logging.basicConfig(level=args.log_level.upper())
 
     # Write port to a well-known file so external tools (MCP clients) can discover it.
     port_file = Path.home() / ".config" / "brainshape" / "port"
@@ -1466,6 +1470,12 @@ if __name__ == "__main__":
         # PyInstaller frozen build: pass the app object directly.
         # String-based import ("brainshape.server:app") fails in frozen envs.
         # reload is incompatible with the object form, but irrelevant here.
-        uvicorn.run(app, host=args.host, port=args.port)
+        uvicorn.run(app, host=args.host, port=args.port, log_level=args.log_level)
     else:
-        uvicorn.run("brainshape.server:app", host=args.host, port=args.port, reload=args.reload)
+        uvicorn.run(
+            "brainshape.server:app",
+            host=args.host,
+            port=args.port,
+            reload=args.reload,
+            log_level=args.log_level,
+        )
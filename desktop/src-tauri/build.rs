@@ -1,3 +1,29 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
 fn main() {
+    build_fake_sidecar();
     tauri_build::build()
 }
+
+/// Compile the fake backend used by `tests/sidecar.rs`. It only uses std,
+/// so plain rustc is enough, and a failure only affects those tests.
+fn build_fake_sidecar() {
+    let source = "tests/support/fake_sidecar.rs";
+    println!("cargo:rerun-if-changed={source}");
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap())
+        .join(format!("fake-sidecar{}", env::consts::EXE_SUFFIX));
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let status = Command::new(rustc)
+        .args(["--edition", "2021", "-o"])
+        .arg(&out)
+        .arg(source)
+        .status();
+    match status {
+        Ok(status) if status.success() => {
+            println!("cargo:rustc-env=FAKE_SIDECAR={}", out.display())
+        }
+        other => println!("cargo:warning=Could not build the fake sidecar: {other:?}"),
+    }
+}
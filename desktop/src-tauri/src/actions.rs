@@ -0,0 +1,179 @@
+//! Registry of user-invokable actions.
+//!
+//! Every action the command palette, global shortcuts, or a future native
+//! menu can run is listed here once, with a stable id and title. The
+//! frontend marks actions unavailable with `set_action_enabled` (e.g.
+//! backend-dependent ones while disconnected). `invoke_action` runs
+//! window actions directly and forwards the rest to the frontend as an
+//! `action-invoked` event carrying the id.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::chaos;
+use crate::error::{AppError, AppResult, LockExt};
+use crate::shortcuts;
+
+struct ActionDef {
+    id: &'static str,
+    title: &'static str,
+}
+
+const ACTIONS: &[ActionDef] = &[
+    ActionDef {
+        id: "new-note",
+        title: "New Note",
+    },
+    ActionDef {
+        id: "view-editor",
+        title: "Switch to Editor",
+    },
+    ActionDef {
+        id: "view-graph",
+        title: "Switch to Graph",
+    },
+    ActionDef {
+        id: "view-memory",
+        title: "Switch to Memory",
+    },
+    ActionDef {
+        id: "view-search",
+        title: "Switch to Search",
+    },
+    ActionDef {
+        id: "open-settings",
+        title: "Open Settings",
+    },
+    ActionDef {
+        id: "sync",
+        title: "Run Sync",
+    },
+    ActionDef {
+        id: "toggle-window",
+        title: "Show / Hide Window",
+    },
+];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ActionInfo {
+    id: &'static str,
+    title: &'static str,
+    enabled: bool,
+    /// Global shortcut bound to this action, if any.
+    shortcut: Option<String>,
+}
+
+#[derive(Default)]
+pub struct ActionState {
+    disabled: Mutex<HashSet<String>>,
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(ActionState::default());
+}
+
+fn find(id: &str) -> AppResult<&'static ActionDef> {
+    ACTIONS
+        .iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown action: {id}")))
+}
+
+pub(crate) fn show_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn toggle_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let focused = window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false);
+    if focused {
+        let _ = window.hide();
+    } else {
+        show_window(app);
+    }
+}
+
+/// Run an action by id. Disabled actions are refused.
+pub fn invoke(app: &AppHandle, id: &str) -> AppResult<()> {
+    let action = find(id)?;
+    let state = app.state::<ActionState>();
+    if state.disabled.lock_or_recover().contains(action.id) {
+        return Err(AppError::Unavailable(format!(
+            "{} is not available right now",
+            action.title
+        )));
+    }
+    match action.id {
+        "toggle-window" => toggle_window(app),
+        id => {
+            show_window(app);
+            chaos::emit(app, "action-invoked", id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Re-enable every action.
+pub fn reset(app: &AppHandle) {
+    app.state::<ActionState>()
+        .disabled
+        .lock_or_recover()
+        .clear();
+}
+
+#[tauri::command]
+pub fn list_actions(app: AppHandle, state: State<'_, ActionState>) -> Vec<ActionInfo> {
+    let disabled = state.disabled.lock_or_recover();
+    ACTIONS
+        .iter()
+        .map(|a| ActionInfo {
+            id: a.id,
+            title: a.title,
+            enabled: !disabled.contains(a.id),
+            shortcut: shortcuts::accelerator_for(&app, a.id),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn invoke_action(app: AppHandle, id: String) -> AppResult<()> {
+    invoke(&app, &id)
+}
+
+#[tauri::command]
+pub fn set_action_enabled(
+    state: State<'_, ActionState>,
+    id: String,
+    enabled: bool,
+) -> AppResult<()> {
+    let action = find(&id)?;
+    let mut disabled = state.disabled.lock_or_recover();
+    if enabled {
+        disabled.remove(action.id);
+    } else {
+        disabled.insert(action.id.to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_unique() {
+        let ids: HashSet<_> = ACTIONS.iter().map(|a| a.id).collect();
+        assert_eq!(ids.len(), ACTIONS.len());
+        assert!(find("sync").is_ok());
+        assert!(find("format-disk").is_err());
+    }
+}
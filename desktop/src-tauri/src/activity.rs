@@ -0,0 +1,84 @@
+//! Keep macOS from throttling the app during long jobs.
+//!
+//! App Nap slows the timers and I/O of apps whose windows are hidden or
+//! covered, which stalls a sync or import the user started and walked away
+//! from. The frontend brackets such jobs with `begin_activity` /
+//! `end_activity`, which hold an `NSProcessInfo` activity assertion. Other
+//! platforms don't nap apps, so there the commands only track ids.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::{AppError, AppResult, LockExt};
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::rc::Retained;
+    use objc2::runtime::{NSObjectProtocol, ProtocolObject};
+    use objc2_foundation::{NSActivityOptions, NSProcessInfo, NSString};
+
+    pub struct Token(Retained<ProtocolObject<dyn NSObjectProtocol>>);
+
+    // SAFETY: the token is an opaque object that is only passed back to
+    // NSProcessInfo, which is thread-safe.
+    unsafe impl Send for Token {}
+
+    /// Still lets the system sleep when idle; only App Nap is held off.
+    pub fn begin(reason: &str) -> Token {
+        let info = NSProcessInfo::processInfo();
+        Token(info.beginActivityWithOptions_reason(
+            NSActivityOptions::UserInitiatedAllowingIdleSystemSleep,
+            &NSString::from_str(reason),
+        ))
+    }
+
+    pub fn end(token: Token) {
+        // SAFETY: the token came from beginActivityWithOptions_reason.
+        unsafe { NSProcessInfo::processInfo().endActivity(&token.0) };
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    pub struct Token;
+
+    pub fn begin(_reason: &str) -> Token {
+        Token
+    }
+
+    pub fn end(_token: Token) {}
+}
+
+#[derive(Default)]
+pub struct Activities {
+    next_id: u64,
+    active: HashMap<u64, platform::Token>,
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(Mutex::new(Activities::default()));
+}
+
+/// Start a long job; returns an id for `end_activity`.
+#[tauri::command]
+pub fn begin_activity(state: State<'_, Mutex<Activities>>, reason: String) -> u64 {
+    let mut activities = state.lock_or_recover();
+    activities.next_id += 1;
+    let id = activities.next_id;
+    activities.active.insert(id, platform::begin(&reason));
+    eprintln!("[activity] Begin {}: {}", id, reason);
+    id
+}
+
+#[tauri::command]
+pub fn end_activity(state: State<'_, Mutex<Activities>>, id: u64) -> AppResult<()> {
+    let token = state
+        .lock_or_recover()
+        .active
+        .remove(&id)
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown activity: {id}")))?;
+    platform::end(token);
+    Ok(())
+}
@@ -0,0 +1,386 @@
+//! Archive import for zipped or tarred vault exports.
+//!
+//! Archives are extracted into a staging directory in the session workspace.
+//! The frontend then hands that directory to the backend's `/import/vault`
+//! endpoint, so the existing import rules (only `.md` files, skip existing)
+//! still decide what actually lands in the notes folder.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::audit::{self, Access};
+use crate::chaos;
+use crate::error::AppResult;
+use crate::paths;
+use crate::tasks::{self, Task};
+use crate::workspace;
+
+/// Free space required on top of the archive's uncompressed size.
+const SPACE_MARGIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Folders that archivers add but that never contain notes.
+const IGNORED_ROOTS: &[&str] = &["__MACOSX"];
+
+#[derive(Debug, PartialEq)]
+enum ArchiveKind {
+    Zip,
+    TarGz,
+}
+
+impl ArchiveKind {
+    fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether `path` has an extension `import_archive` accepts.
+pub(crate) fn is_archive(path: &Path) -> bool {
+    ArchiveKind::detect(path).is_some()
+}
+
+/// Result of a finished extraction.
+#[derive(Debug, Serialize)]
+pub struct ArchiveImport {
+    /// Directory to pass to `/import/vault`.
+    path: String,
+    files: usize,
+    markdown_files: usize,
+}
+
+/// Join an archive entry name onto `dest`, returning `None` for absolute
+/// paths, `..` components, or anything else that would escape `dest`.
+pub(crate) fn safe_join(dest: &Path, name: &Path) -> Option<PathBuf> {
+    let mut out = dest.to_path_buf();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (out != dest).then_some(out)
+}
+
+/// Total uncompressed size of all regular files in the archive.
+fn uncompressed_size(archive: &Path, kind: &ArchiveKind) -> io::Result<u64> {
+    match kind {
+        ArchiveKind::Zip => {
+            let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+            let mut total = 0;
+            for i in 0..zip.len() {
+                total += zip.by_index_raw(i)?.size();
+            }
+            Ok(total)
+        }
+        ArchiveKind::TarGz => {
+            let mut tar = tar::Archive::new(GzDecoder::new(File::open(archive)?));
+            let mut total = 0;
+            for entry in tar.entries()? {
+                let entry = entry?;
+                if entry.header().entry_type().is_file() {
+                    total += entry.size();
+                }
+            }
+            Ok(total)
+        }
+    }
+}
+
+/// Copy one entry to `target`, failing if the archive inflates past `limit`.
+fn write_entry(
+    reader: &mut dyn Read,
+    target: &Path,
+    done: &mut u64,
+    limit: u64,
+    progress: &mut dyn FnMut(u64) -> io::Result<()>,
+) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut out = File::create(target)?;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        *done += n as u64;
+        if *done > limit {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "archive expands beyond its declared size",
+            ));
+        }
+        out.write_all(&buf[..n])?;
+        progress(*done)?;
+    }
+}
+
+/// Extract regular files and directories into `dest`. Symlinks, devices,
+/// and entries whose paths escape `dest` are skipped. An error from
+/// `progress` stops the extraction.
+fn extract(
+    archive: &Path,
+    kind: &ArchiveKind,
+    dest: &Path,
+    limit: u64,
+    progress: &mut dyn FnMut(u64) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut done = 0;
+    match kind {
+        ArchiveKind::Zip => {
+            let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+            for i in 0..zip.len() {
+                let mut file = zip.by_index(i)?;
+                let Some(target) = safe_join(dest, Path::new(file.name())) else {
+                    eprintln!("[archive] Skipping unsafe entry: {}", file.name());
+                    continue;
+                };
+                if file.is_dir() {
+                    fs::create_dir_all(&target)?;
+                } else if !file.is_symlink() {
+                    write_entry(&mut file, &target, &mut done, limit, progress)?;
+                }
+            }
+        }
+        ArchiveKind::TarGz => {
+            let mut tar = tar::Archive::new(GzDecoder::new(File::open(archive)?));
+            for entry in tar.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.into_owned();
+                let Some(target) = safe_join(dest, &name) else {
+                    eprintln!("[archive] Skipping unsafe entry: {}", name.display());
+                    continue;
+                };
+                let entry_type = entry.header().entry_type();
+                if entry_type.is_dir() {
+                    fs::create_dir_all(&target)?;
+                } else if entry_type.is_file() {
+                    write_entry(&mut entry, &target, &mut done, limit, progress)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Count all files and markdown files under `dir`.
+pub(crate) fn scan(dir: &Path) -> io::Result<(usize, usize)> {
+    let (mut files, mut markdown) = (0, 0);
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            let (f, m) = scan(&path)?;
+            files += f;
+            markdown += m;
+        } else {
+            files += 1;
+            if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+            {
+                markdown += 1;
+            }
+        }
+    }
+    Ok((files, markdown))
+}
+
+/// Most exports wrap everything in a single top-level folder; use that
+/// folder as the vault root so imported notes don't gain an extra level.
+fn vault_root(dest: &Path) -> io::Result<PathBuf> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dest)? {
+        let entry = entry?;
+        if !IGNORED_ROOTS.iter().any(|name| entry.file_name() == *name) {
+            entries.push(entry.path());
+        }
+    }
+    match entries.as_slice() {
+        [only] if only.is_dir() => Ok(only.clone()),
+        _ => Ok(dest.to_path_buf()),
+    }
+}
+
+fn run_import(
+    task: &Task,
+    archive: &Path,
+    staging: &Path,
+    quota_remaining: u64,
+) -> io::Result<ArchiveImport> {
+    let kind = ArchiveKind::detect(archive).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Unsupported archive type (expected .zip or .tar.gz)",
+        )
+    })?;
+
+    let total = uncompressed_size(archive, &kind)?;
+    if total > quota_remaining {
+        return Err(io::Error::new(
+            io::ErrorKind::StorageFull,
+            format!("Archive needs {total} bytes, temporary workspace has {quota_remaining} left"),
+        ));
+    }
+    let available = fs4::available_space(staging)?;
+    if total + SPACE_MARGIN_BYTES > available {
+        return Err(io::Error::new(
+            io::ErrorKind::StorageFull,
+            format!("Not enough disk space: archive needs {total} bytes, {available} available"),
+        ));
+    }
+
+    extract(archive, &kind, staging, total, &mut |done| {
+        task.progress(done, total);
+        task.check()
+    })?;
+
+    let root = vault_root(staging)?;
+    let (files, markdown_files) = scan(&root)?;
+    Ok(ArchiveImport {
+        path: root.to_string_lossy().into_owned(),
+        files,
+        markdown_files,
+    })
+}
+
+/// Extract a `.zip` or `.tar.gz` vault export into a fresh staging directory.
+/// Call `release_workspace_dir` with the returned path once it's imported.
+#[tauri::command]
+pub async fn import_archive(app: AppHandle, path: String) -> AppResult<ArchiveImport> {
+    chaos::check_disk(&app)?;
+    let source = paths::authorize(&app, &path, false)?;
+    audit::record(&app, "import_archive", Access::Read, &source);
+    let label = tasks::file_label(&source);
+    let handle = app.clone();
+    tasks::run(&app, "import_archive", label, move |task| {
+        let workspace = workspace::get(&handle)?;
+        let staging = workspace.allocate("import")?;
+        let result = run_import(task, &source, &staging, workspace.remaining());
+        if result.is_err() {
+            let _ = workspace.release(&staging);
+        }
+        Ok(result?)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("brainshape-archive-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, body) in entries {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn detects_kind_from_extension() {
+        assert_eq!(
+            ArchiveKind::detect(Path::new("Vault.ZIP")),
+            Some(ArchiveKind::Zip)
+        );
+        assert_eq!(
+            ArchiveKind::detect(Path::new("v.tar.gz")),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(
+            ArchiveKind::detect(Path::new("v.tgz")),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(ArchiveKind::detect(Path::new("v.rar")), None);
+    }
+
+    #[test]
+    fn safe_join_rejects_escapes() {
+        let dest = Path::new("/tmp/staging");
+        assert_eq!(
+            safe_join(dest, Path::new("a/b.md")),
+            Some(dest.join("a/b.md"))
+        );
+        assert_eq!(
+            safe_join(dest, Path::new("./a.md")),
+            Some(dest.join("a.md"))
+        );
+        assert_eq!(safe_join(dest, Path::new("../evil.md")), None);
+        assert_eq!(safe_join(dest, Path::new("a/../../evil.md")), None);
+        assert_eq!(safe_join(dest, Path::new("/etc/passwd")), None);
+        assert_eq!(safe_join(dest, Path::new(".")), None);
+    }
+
+    #[test]
+    fn extracts_zip_and_skips_traversal() {
+        let dir = temp_dir("zip");
+        let archive = dir.join("vault.zip");
+        write_zip(
+            &archive,
+            &[
+                ("Vault/Note.md", "# Note"),
+                ("Vault/img.png", "png"),
+                ("../evil.md", "x"),
+            ],
+        );
+        let dest = dir.join("out");
+        fs::create_dir_all(&dest).unwrap();
+
+        let total = uncompressed_size(&archive, &ArchiveKind::Zip).unwrap();
+        extract(&archive, &ArchiveKind::Zip, &dest, total, &mut |_| Ok(())).unwrap();
+
+        let root = vault_root(&dest).unwrap();
+        assert_eq!(root, dest.join("Vault"));
+        assert_eq!(scan(&root).unwrap(), (2, 1));
+        assert!(!dir.join("evil.md").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn extraction_stops_past_declared_size() {
+        let dir = temp_dir("limit");
+        let archive = dir.join("vault.zip");
+        write_zip(&archive, &[("Note.md", "0123456789")]);
+        let dest = dir.join("out");
+
+        let err = extract(&archive, &ArchiveKind::Zip, &dest, 4, &mut |_| Ok(())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn extraction_stops_when_cancelled() {
+        let dir = temp_dir("cancel");
+        let archive = dir.join("vault.zip");
+        write_zip(&archive, &[("A.md", "# A"), ("B.md", "# B")]);
+        let dest = dir.join("out");
+
+        let cancel = || Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+        let err = extract(&archive, &ArchiveKind::Zip, &dest, 100, &mut |_| cancel()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(!dest.join("B.md").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
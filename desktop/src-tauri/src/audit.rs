@@ -0,0 +1,145 @@
+//! Append-only log of file access performed by Rust commands, for
+//! accountability: who read, wrote, opened, or deleted which path, when,
+//! and through which command.
+//!
+//! One JSON object per line in `audit.jsonl` in the app log dir. Entries
+//! are only ever appended; `export_audit_log` copies the file out. Note
+//! edits go through the backend's API and aren't covered here.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{AppError, AppResult, LockExt};
+use crate::paths;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Access {
+    Read,
+    Write,
+    Open,
+    Delete,
+}
+
+#[derive(Debug, Serialize)]
+struct Entry<'a> {
+    /// Unix timestamp in milliseconds.
+    time: u128,
+    user: &'a str,
+    command: &'a str,
+    access: Access,
+    path: String,
+}
+
+pub struct AuditLog {
+    path: PathBuf,
+    file: Mutex<Option<File>>,
+    user: String,
+}
+
+impl AuditLog {
+    fn open(path: PathBuf) -> Self {
+        let file = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| OpenOptions::new().create(true).append(true).open(&path))
+            .map_err(|e| eprintln!("[audit] Could not open {}: {}", path.display(), e))
+            .ok();
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".into());
+        Self {
+            path,
+            file: Mutex::new(file),
+            user,
+        }
+    }
+
+    fn append(&self, command: &str, access: Access, path: &Path) {
+        let entry = Entry {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or_default(),
+            user: &self.user,
+            command,
+            access,
+            path: path.to_string_lossy().into_owned(),
+        };
+        let Ok(mut line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        line.push('\n');
+        if let Some(file) = self.file.lock_or_recover().as_mut() {
+            if let Err(e) = file.write_all(line.as_bytes()) {
+                eprintln!("[audit] Failed to record {} {}: {}", command, entry.path, e);
+            }
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    match app.path().app_log_dir() {
+        Ok(dir) => {
+            app.manage(AuditLog::open(dir.join("audit.jsonl")));
+        }
+        Err(e) => eprintln!("[audit] No log dir; file access won't be recorded: {}", e),
+    }
+}
+
+/// Record that `command` accessed `path`.
+pub fn record(app: &AppHandle, command: &str, access: Access, path: &Path) {
+    if let Some(log) = app.try_state::<AuditLog>() {
+        log.append(command, access, path);
+    }
+}
+
+/// Copy the audit log to `path` (picked with `pick_save_file`).
+#[tauri::command]
+pub fn export_audit_log(app: AppHandle, path: String) -> AppResult<u64> {
+    let dest = paths::authorize(&app, &path, true)?;
+    let log = app.try_state::<AuditLog>().ok_or_else(|| {
+        AppError::Unavailable("Audit log unavailable: the app log folder couldn't be found".into())
+    })?;
+    // Hold the lock so the copy never ends mid-line.
+    let guard = log.file.lock_or_recover();
+    let bytes = fs::copy(&log.path, &dest)?;
+    drop(guard);
+    record(&app, "export_audit_log", Access::Write, &dest);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_json_lines() {
+        let path = std::env::temp_dir().join(format!(
+            "brainshape-audit-{}/audit.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        let log = AuditLog::open(path.clone());
+        log.append("import_archive", Access::Read, Path::new("/tmp/vault.zip"));
+        drop(log);
+        let log = AuditLog::open(path.clone());
+        log.append("create_backup", Access::Write, Path::new("/tmp/backup.zip"));
+
+        let text = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["command"], "import_archive");
+        assert_eq!(lines[1]["access"], "write");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+//! Hooks for end-to-end tests, behind the `automation` feature flag (set
+//! `BRAINSHAPE_FLAGS=automation` in CI).
+//!
+//! Windows get titles that name them, so a driver can find the startup
+//! error window without guessing, and `automation_reset_state` puts the
+//! shell back to a fresh install between tests. Screenshots are left to
+//! the WebDriver session (`tauri-driver` supports Take Screenshot).
+
+use tauri::{AppHandle, Manager};
+
+use crate::error::{AppError, AppResult};
+use crate::{actions, chaos, flags, notifications, recovery, shortcuts};
+
+const FLAG: &str = "automation";
+
+pub fn enabled(app: &AppHandle) -> bool {
+    flags::is_enabled(app, FLAG)
+}
+
+/// Title for the window `label`: `Brainshape`, or `Brainshape [label]`
+/// under automation.
+pub fn window_title(app: &AppHandle, label: &str) -> String {
+    if enabled(app) {
+        format!("Brainshape [{label}]")
+    } else {
+        "Brainshape".into()
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    if !enabled(app) {
+        return;
+    }
+    eprintln!("[automation] Test hooks enabled");
+    if let Some(main) = app.get_webview_window("main") {
+        let _ = main.set_title(&window_title(app, "main"));
+    }
+}
+
+/// Clear notifications, recovery snapshots, shortcut bindings, and disabled
+/// actions, then emit `automation-reset` so the frontend reloads. Notes and
+/// backend settings belong to the backend and are left alone.
+#[tauri::command]
+pub fn automation_reset_state(app: AppHandle) -> AppResult<()> {
+    if !enabled(&app) {
+        return Err(AppError::Unavailable(
+            "Automation hooks need the automation feature flag".into(),
+        ));
+    }
+    eprintln!("[automation] Resetting state");
+    notifications::clear(&app);
+    recovery::discard_all(&app);
+    shortcuts::reset(&app)?;
+    actions::reset(&app);
+    chaos::emit(&app, "automation-reset", ())?;
+    Ok(())
+}
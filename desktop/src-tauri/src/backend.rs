@@ -0,0 +1,791 @@
+//! The Python backend sidecar: locating, spawning, and stopping it.
+//!
+//! A failed start doesn't abort the app. The main window stays hidden and a
+//! small `startup-error` window explains what went wrong and offers to open
+//! the log, retry, or continue without a backend. That includes a sidecar
+//! that exits or never answers before becoming ready; one that exits later
+//! is reported as a notification. Sidecar output is forwarded to the console
+//! and to `backend.log` in the app log directory.
+//!
+//! Nothing here blocks the main thread: locating, verifying, and spawning
+//! the sidecar run on a worker thread, readiness is polled by the monitor
+//! thread, and `backend-connection` tells the frontend the moment the
+//! backend is up instead of leaving it to its next health poll.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::automation;
+use crate::chaos;
+use crate::error::{AppError, AppResult, LockExt};
+use crate::flags;
+use crate::python_env;
+use crate::settings;
+use crate::sidecar::{self, Host, Sidecar};
+use crate::signature;
+use crate::{notifications, workspace};
+
+/// Default port for the Brainshape backend server.
+/// Fixed so external MCP clients can reliably connect.
+pub const DEFAULT_PORT: u16 = 52836;
+
+/// Label of the startup-error window. Closing it quits the app, rather
+/// than leaving the hidden main window running.
+pub const ERROR_WINDOW: &str = "startup-error";
+
+/// The bundled server unpacks itself on first launch, which can be slow.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(90);
+const EXIT_POLL: Duration = Duration::from_secs(1);
+const CONNECTION_POLL: Duration = Duration::from_secs(2);
+
+/// How to start the dev server by hand, from the repository root.
+const DEV_SERVER_COMMAND: &str = "uv run python -m brainshape.server";
+/// Environment variable with a command the app should run as the dev server.
+const DEV_BACKEND_VAR: &str = "BRAINSHAPE_DEV_BACKEND";
+
+/// The backend the shell points the frontend at.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Connection {
+    /// The bundled sidecar, which the shell starts, restarts, and stops.
+    Sidecar { port: u16 },
+    /// A backend running on its own: from `--backend-url`, the mock, the
+    /// developer's server, or a saved connection (`name`).
+    External { name: Option<String>, port: u16 },
+}
+
+impl Connection {
+    pub fn port(&self) -> u16 {
+        match self {
+            Self::Sidecar { port } | Self::External { port, .. } => *port,
+        }
+    }
+}
+
+/// State shared between the Tauri setup and commands.
+pub struct BackendState {
+    connection: Connection,
+    /// Whether the connection may be switched at runtime; not when it was
+    /// chosen on the command line.
+    switchable: bool,
+    verbose: bool,
+    /// `--allow-unsigned-backend`: skip the sidecar signature check.
+    allow_unsigned: bool,
+    child: Option<Sidecar>,
+    /// Bumped on every launch and switch so a monitor thread can tell it's
+    /// stale.
+    generation: u64,
+    startup_error: Option<StartupError>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StartupError {
+    message: String,
+    hint: Option<String>,
+}
+
+impl From<&AppError> for StartupError {
+    fn from(e: &AppError) -> Self {
+        let hint = match e {
+            AppError::NotFound(_) => {
+                Some("The installation looks incomplete. Reinstalling Brainshape should fix it.")
+            }
+            AppError::PermissionDenied(_) => Some(
+                "The bundled backend may have been replaced. Reinstall Brainshape from the official download.",
+            ),
+            _ => e.hint(),
+        };
+        Self {
+            message: e.to_string(),
+            hint: hint.map(String::from),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct ConnectionChanged {
+    connected: bool,
+}
+
+/// Manage state for a backend the shell doesn't spawn, and watch it.
+fn manage_external(app: &AppHandle, state: BackendState) {
+    let port = state.connection.port();
+    app.manage(Mutex::new(state));
+    watch_connection(app.clone(), port, 0);
+}
+
+/// Use a backend started with `--backend-url`, or the mock. The command
+/// line chose it, so it can't be switched.
+pub fn use_external(app: &AppHandle, port: u16) {
+    manage_external(
+        app,
+        BackendState {
+            connection: Connection::External { name: None, port },
+            switchable: false,
+            verbose: false,
+            allow_unsigned: false,
+            child: None,
+            generation: 0,
+            startup_error: None,
+        },
+    );
+}
+
+/// Use the saved connection `name` at launch. The options apply if the user
+/// switches to the bundled sidecar later.
+pub fn use_saved(app: &AppHandle, name: String, port: u16, verbose: bool, allow_unsigned: bool) {
+    manage_external(
+        app,
+        BackendState {
+            connection: Connection::External {
+                name: Some(name),
+                port,
+            },
+            switchable: true,
+            verbose,
+            allow_unsigned,
+            child: None,
+            generation: 0,
+            startup_error: None,
+        },
+    );
+}
+
+/// Debug builds use the developer's own server on the default port. If
+/// nothing answers there, run `BRAINSHAPE_DEV_BACKEND` (e.g. `uv run server`)
+/// from the repository root as a child that stops with the app; without it,
+/// say how to start the server.
+pub fn use_dev(app: &AppHandle) {
+    manage_external(
+        app,
+        BackendState {
+            connection: Connection::External {
+                name: None,
+                port: DEFAULT_PORT,
+            },
+            switchable: true,
+            verbose: false,
+            allow_unsigned: false,
+            child: None,
+            generation: 0,
+            startup_error: None,
+        },
+    );
+    let app = app.clone();
+    std::thread::spawn(move || start_dev(&app));
+}
+
+/// Split a command line into words the way a POSIX shell would, minus
+/// expansions: whitespace separates words, quotes group them, and outside
+/// single quotes a backslash escapes the next character. Backslashes are
+/// kept as-is on Windows, where they separate paths.
+fn split_command(line: &str) -> AppResult<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None | Some('"'), '\\') if !cfg!(windows) => {
+                let escaped = chars.next().ok_or_else(|| {
+                    AppError::InvalidInput(format!("Trailing backslash in `{line}`"))
+                })?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (_, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(AppError::InvalidInput(format!(
+            "Unterminated quote in `{line}`"
+        )));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+fn start_dev(app: &AppHandle) {
+    if sidecar::is_healthy(DEFAULT_PORT) {
+        return;
+    }
+    let configured = std::env::var(DEV_BACKEND_VAR)
+        .ok()
+        .filter(|c| !c.trim().is_empty());
+    let Some(configured) = configured else {
+        let hint = format!(
+            "Start it from the repository root with `{DEV_SERVER_COMMAND}`, or set \
+             {DEV_BACKEND_VAR} to a command the app should run for you."
+        );
+        eprintln!(
+            "[backend] No dev backend on port {}. {}",
+            DEFAULT_PORT, hint
+        );
+        notifications::notify(
+            app,
+            notifications::Level::Warning,
+            "No dev backend running",
+            Some(hint),
+        );
+        return;
+    };
+
+    let spawned = split_command(&configured).and_then(|words| {
+        let (program, args) = words
+            .split_first()
+            .ok_or_else(|| AppError::InvalidInput("The command is empty".into()))?;
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../.."));
+        let on_output: sidecar::OutputHandler =
+            Arc::new(|line, _| eprintln!("[dev-backend] {}", line));
+        Sidecar::spawn(command, DEFAULT_PORT, on_output)
+    });
+    match spawned {
+        Ok(child) => {
+            eprintln!("[backend] Started dev backend: {}", configured);
+            app.state::<Mutex<BackendState>>().lock_or_recover().child = Some(child);
+        }
+        Err(e) => {
+            eprintln!("[backend] {}: {}", DEV_BACKEND_VAR, e);
+            notifications::notify(
+                app,
+                notifications::Level::Error,
+                "Dev backend failed to start",
+                Some(format!("{DEV_BACKEND_VAR}={configured}: {e}")),
+            );
+        }
+    }
+}
+
+/// An external backend can be restarted behind the app's back (e.g. the
+/// developer rerunning their server). Emit `backend-connection` whenever it
+/// goes away or comes back so the frontend can reconnect right away and
+/// drop state the old process held, like agent sessions. Stops once the
+/// connection is switched (`generation` is stale).
+fn watch_connection(app: AppHandle, port: u16, generation: u64) {
+    std::thread::spawn(move || {
+        let mut connected = sidecar::is_healthy(port);
+        if !connected {
+            eprintln!("[backend] Nothing answering on port {} yet", port);
+        }
+        loop {
+            std::thread::sleep(CONNECTION_POLL);
+            let state = app.state::<Mutex<BackendState>>();
+            if state.lock_or_recover().generation != generation {
+                return;
+            }
+            let now = sidecar::is_healthy(port);
+            if now == connected {
+                continue;
+            }
+            connected = now;
+            eprintln!(
+                "[backend] Backend on port {} {}",
+                port,
+                if connected { "is back" } else { "went away" }
+            );
+            let _ = chaos::emit(&app, "backend-connection", ConnectionChanged { connected });
+        }
+    });
+}
+
+/// Spawn the bundled sidecar, or show the startup-error window if that fails.
+pub fn start(app: &AppHandle, verbose: bool, allow_unsigned: bool) {
+    app.manage(Mutex::new(BackendState {
+        connection: Connection::Sidecar { port: DEFAULT_PORT },
+        switchable: true,
+        verbose,
+        allow_unsigned,
+        child: None,
+        generation: 0,
+        startup_error: None,
+    }));
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if Host::detect().translated {
+            notifications::notify(
+                &app,
+                notifications::Level::Warning,
+                "Running under Rosetta",
+                Some(
+                    "This is the Intel build of Brainshape. The Apple silicon build is faster and uses less battery."
+                        .into(),
+                ),
+            );
+        }
+        if let Err(e) = launch(&app) {
+            show_startup_error(&app, &e);
+        }
+    });
+}
+
+/// Kill the sidecar, if one is running.
+pub fn stop(app: &AppHandle) {
+    if let Some(state) = app.try_state::<Mutex<BackendState>>() {
+        if let Some(mut child) = state.lock_or_recover().child.take() {
+            child.kill();
+        }
+    }
+}
+
+/// Kill the sidecar but leave it in place, so the exit monitor reports it
+/// like a real crash.
+pub fn crash(app: &AppHandle) -> AppResult<()> {
+    let state = app.state::<Mutex<BackendState>>();
+    let mut state = state.lock_or_recover();
+    match state.child.as_mut() {
+        Some(child) => {
+            child.kill();
+            Ok(())
+        }
+        None => Err(AppError::Unavailable(
+            "No backend process is running under the app".into(),
+        )),
+    }
+}
+
+/// Run `work` with the backend stopped, so the files it keeps open (the
+/// SurrealDB database) are at rest, then start the sidecar again. A backend
+/// the app didn't start can't be stopped from here, so that fails while it
+/// answers.
+pub fn while_stopped<T>(app: &AppHandle, work: impl FnOnce() -> T) -> AppResult<T> {
+    let state = app.state::<Mutex<BackendState>>();
+    let connection = state.lock_or_recover().connection.clone();
+    if let Connection::External { port, .. } = connection {
+        if sidecar::is_healthy(port) {
+            return Err(AppError::Unavailable(format!(
+                "The backend on port {port} isn't run by Brainshape. Stop it, or switch \
+                 to the built-in backend, first."
+            )));
+        }
+        return Ok(work());
+    }
+
+    {
+        let mut state = state.lock_or_recover();
+        // A new generation, so the exit monitor doesn't report a crash.
+        state.generation += 1;
+        if let Some(mut child) = state.child.take() {
+            eprintln!("[backend] Stopping the backend for a while");
+            child.kill();
+        }
+    }
+    let result = work();
+    if let Err(e) = launch(app) {
+        show_startup_error(app, &e);
+    }
+    Ok(result)
+}
+
+/// The best sidecar build bundled for this machine; see [`Host::variants`].
+fn sidecar_path(app: &AppHandle) -> AppResult<PathBuf> {
+    let resources = app.path().resource_dir()?.join("resources");
+    let exe = format!("brainshape-server{}", std::env::consts::EXE_SUFFIX);
+    let variants = Host::detect().variants();
+    for variant in &variants {
+        let path = resources.join(variant).join(&exe);
+        if path.exists() {
+            eprintln!("[backend] Using {}", variant);
+            return Ok(path);
+        }
+    }
+    Err(AppError::NotFound(format!(
+        "Backend not found in {} (looked for {})",
+        resources.display(),
+        variants.join(", ")
+    )))
+}
+
+fn log_path(app: &AppHandle) -> AppResult<PathBuf> {
+    Ok(app.path().app_log_dir()?.join("backend.log"))
+}
+
+/// Start a fresh log for this launch. Logging is best-effort: without a
+/// writable log dir, output still goes to the console.
+fn open_log(app: &AppHandle) -> Option<Arc<Mutex<File>>> {
+    let path = log_path(app).ok()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    let file = File::create(&path)
+        .map_err(|e| eprintln!("[backend] Could not create {}: {}", path.display(), e))
+        .ok()?;
+    Some(Arc::new(Mutex::new(file)))
+}
+
+fn log_line(log: &Option<Arc<Mutex<File>>>, line: &str) {
+    if let Some(file) = log {
+        let _ = writeln!(file.lock_or_recover(), "{}", line);
+    }
+}
+
+/// Spawn the sidecar, or the backend from the configured Python environment
+/// (see `python_env`), record it in the managed state, and start watching
+/// it. Blocks while the signature is checked or the environment probed, so
+/// call it off the main thread.
+fn launch(app: &AppHandle) -> AppResult<()> {
+    let state = app.state::<Mutex<BackendState>>();
+    let (port, verbose, allow_unsigned) = {
+        let state = state.lock_or_recover();
+        (state.connection.port(), state.verbose, state.allow_unsigned)
+    };
+    let log = open_log(app);
+
+    let command = match settings::get(app).python_interpreter {
+        Some(python) => {
+            log_line(
+                &log,
+                &format!("[shell] Running the backend with {}", python.display()),
+            );
+            python_env::server_command(&python)
+        }
+        None => sidecar_path(app).and_then(|sidecar_exe| {
+            if allow_unsigned {
+                log_line(&log, "[shell] Skipping the backend signature check");
+            } else {
+                signature::verify(&sidecar_exe)?;
+            }
+            Ok(Command::new(&sidecar_exe))
+        }),
+    };
+    let result = command.and_then(|mut command| {
+        command.args(["--host", "127.0.0.1", "--port", &port.to_string()]);
+        if verbose {
+            command.args(["--log-level", "debug"]);
+        }
+
+        // Keep the backend's temp files in the session workspace so
+        // they're cleaned up even if the backend crashes.
+        if let Some(tmp) = app
+            .try_state::<workspace::Workspace>()
+            .and_then(|ws| ws.backend_tmp().ok())
+        {
+            command
+                .env("TMPDIR", &tmp)
+                .env("TEMP", &tmp)
+                .env("TMP", &tmp);
+        }
+
+        if flags::is_enabled(app, "backend-console") {
+            log_line(&log, "[shell] Backend output is going to its console");
+            return Sidecar::spawn_in_console(command, port);
+        }
+        let output_log = log.clone();
+        let on_output: sidecar::OutputHandler = Arc::new(move |line, stderr| {
+            if stderr {
+                eprintln!("[backend] {}", line);
+            } else {
+                println!("[backend] {}", line);
+            }
+            log_line(&output_log, line);
+        });
+        Sidecar::spawn(command, port, on_output)
+    });
+
+    let child = match result {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("[backend] {}", e);
+            log_line(&log, &format!("[shell] {}", e));
+            return Err(e);
+        }
+    };
+
+    let generation = {
+        let mut state = state.lock_or_recover();
+        state.child = Some(child);
+        state.generation += 1;
+        state.startup_error = None;
+        state.generation
+    };
+    monitor(app.clone(), port, generation, log);
+    Ok(())
+}
+
+/// Watch the sidecar from `launch` number `generation`: show the startup
+/// error if it never becomes ready, and notify if it exits afterwards.
+/// Stops once the sidecar is stopped or replaced.
+fn monitor(app: AppHandle, port: u16, generation: u64, log: Option<Arc<Mutex<File>>>) {
+    std::thread::spawn(move || {
+        let state = app.state::<Mutex<BackendState>>();
+        // `None` once this sidecar is no longer the current one.
+        let exited = || {
+            let mut state = state.lock_or_recover();
+            if state.generation != generation {
+                return None;
+            }
+            Some(state.child.as_mut().and_then(Sidecar::exited))
+        };
+
+        let mut stale = false;
+        let ready = sidecar::wait_until_ready(port, STARTUP_TIMEOUT, || match exited() {
+            None => {
+                stale = true;
+                Err(AppError::Internal("replaced".into()))
+            }
+            Some(Some(status)) => Err(sidecar::exited_during_startup(status)),
+            Some(None) => Ok(()),
+        });
+        if stale {
+            return;
+        }
+        let ready = ready.and_then(|()| {
+            // The check can block on a connect; don't hold the lock for it.
+            let check = state
+                .lock_or_recover()
+                .child
+                .as_ref()
+                .map(Sidecar::loopback_check);
+            let Some(Err(e)) = check.map(sidecar::LoopbackCheck::run) else {
+                return Ok(());
+            };
+            let mut state = state.lock_or_recover();
+            if state.generation != generation {
+                stale = true;
+            } else if let Some(mut child) = state.child.take() {
+                child.kill();
+            }
+            Err(e)
+        });
+        if stale {
+            return;
+        }
+        if let Err(e) = ready {
+            eprintln!("[backend] {}", e);
+            log_line(&log, &format!("[shell] {}", e));
+            show_startup_error(&app, &e);
+            return;
+        }
+        let _ = chaos::emit(
+            &app,
+            "backend-connection",
+            ConnectionChanged { connected: true },
+        );
+
+        loop {
+            std::thread::sleep(EXIT_POLL);
+            match exited() {
+                None => return,
+                Some(None) => {}
+                Some(Some(status)) => {
+                    let message = format!("The backend exited unexpectedly ({status})");
+                    eprintln!("[backend] {}", message);
+                    log_line(&log, &format!("[shell] {}", message));
+                    notifications::notify(
+                        &app,
+                        notifications::Level::Error,
+                        "Backend stopped",
+                        Some(message),
+                    );
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Hide the main window and explain the failure in the startup-error window.
+fn show_startup_error(app: &AppHandle, e: &AppError) {
+    app.state::<Mutex<BackendState>>()
+        .lock_or_recover()
+        .startup_error = Some(e.into());
+    notifications::notify(
+        app,
+        notifications::Level::Error,
+        "Backend failed to start",
+        Some(e.to_string()),
+    );
+
+    if app.get_webview_window(ERROR_WINDOW).is_some() {
+        return;
+    }
+    let window = WebviewWindowBuilder::new(
+        app,
+        ERROR_WINDOW,
+        WebviewUrl::App("index.html?view=startup-error".into()),
+    )
+    .title(automation::window_title(app, ERROR_WINDOW))
+    .inner_size(480.0, 320.0)
+    .resizable(false)
+    .center()
+    .build();
+    match window {
+        Ok(_) => {
+            if let Some(main) = app.get_webview_window("main") {
+                let _ = main.hide();
+            }
+        }
+        // Without the error window, fall back to the main window; the
+        // notification above still explains what happened.
+        Err(e) => eprintln!("[backend] Could not open the startup error window: {}", e),
+    }
+}
+
+/// Close the startup-error window and show the main window.
+fn dismiss_startup_error(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(ERROR_WINDOW) {
+        // `destroy` skips `CloseRequested`, which would quit the app.
+        let _ = window.destroy();
+    }
+    crate::actions::show_window(app);
+}
+
+/// The current connection.
+pub fn connection(app: &AppHandle) -> Connection {
+    app.state::<Mutex<BackendState>>()
+        .lock_or_recover()
+        .connection
+        .clone()
+}
+
+/// Point the shell at `connection`: stop the sidecar or dev backend if one
+/// is running, then launch the sidecar or start watching the external
+/// backend. Blocks while a sidecar is verified, so call it off the main
+/// thread. A sidecar that fails to launch is reported like at startup, with
+/// the startup-error window. Either way emits `backend-switched` so the
+/// frontend reloads against the new connection.
+pub fn switch(app: &AppHandle, connection: Connection) -> AppResult<()> {
+    let state = app.state::<Mutex<BackendState>>();
+    let generation = {
+        let mut state = state.lock_or_recover();
+        if !state.switchable {
+            return Err(AppError::Unavailable(
+                "The backend was chosen on the command line".into(),
+            ));
+        }
+        if let Some(mut child) = state.child.take() {
+            child.kill();
+        }
+        state.connection = connection.clone();
+        state.generation += 1;
+        state.startup_error = None;
+        state.generation
+    };
+    eprintln!("[backend] Switching to {:?}", connection);
+    let result = match &connection {
+        Connection::Sidecar { .. } => launch(app).inspect_err(|e| {
+            let _ = chaos::emit(
+                app,
+                "backend-connection",
+                ConnectionChanged { connected: false },
+            );
+            show_startup_error(app, e);
+        }),
+        Connection::External { name, port } => {
+            watch_connection(app.clone(), *port, generation);
+            // Back to the built-in backend of a debug build: find or run
+            // the dev server, as at launch.
+            if name.is_none() && cfg!(debug_assertions) {
+                let app = app.clone();
+                std::thread::spawn(move || start_dev(&app));
+            }
+            Ok(())
+        }
+    };
+    let _ = chaos::emit(app, "backend-switched", connection);
+    result
+}
+
+/// Returns the port the Python backend is listening on.
+#[tauri::command]
+pub fn get_backend_port(state: State<'_, Mutex<BackendState>>) -> u16 {
+    state.lock_or_recover().connection.port()
+}
+
+/// Why the backend failed to start, if it did.
+#[tauri::command]
+pub fn get_startup_error(state: State<'_, Mutex<BackendState>>) -> Option<StartupError> {
+    state.lock_or_recover().startup_error.clone()
+}
+
+/// Try spawning the sidecar again; on success the main window is shown.
+#[tauri::command]
+pub async fn retry_backend(app: AppHandle) -> AppResult<()> {
+    let connection = app
+        .state::<Mutex<BackendState>>()
+        .lock_or_recover()
+        .connection
+        .clone();
+    if !matches!(connection, Connection::Sidecar { .. }) {
+        return Err(AppError::Unavailable(
+            "This backend isn't managed by the desktop app".into(),
+        ));
+    }
+    stop(&app);
+    let handle = app.clone();
+    match tauri::async_runtime::spawn_blocking(move || launch(&handle)).await? {
+        Ok(()) => {
+            dismiss_startup_error(&app);
+            Ok(())
+        }
+        Err(e) => {
+            app.state::<Mutex<BackendState>>()
+                .lock_or_recover()
+                .startup_error = Some((&e).into());
+            Err(e)
+        }
+    }
+}
+
+/// Continue to the main window with no backend; it shows as disconnected.
+#[tauri::command]
+pub fn run_without_backend(app: AppHandle) {
+    dismiss_startup_error(&app);
+}
+
+/// Open `backend.log` in the default text viewer.
+#[tauri::command]
+pub fn open_backend_logs(app: AppHandle) -> AppResult<()> {
+    let path = log_path(&app)?;
+    if !path.exists() {
+        return Err(AppError::NotFound(
+            "No backend log has been written yet".into(),
+        ));
+    }
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| AppError::Unavailable(format!("Could not open the log: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn startup_error_hints_at_reinstall_for_missing_sidecar() {
+        let missing = StartupError::from(&AppError::NotFound("Backend not found".into()));
+        assert!(missing.hint.unwrap().contains("Reinstalling"));
+        let spawn = StartupError::from(&AppError::Internal("Could not start".into()));
+        assert_eq!(spawn.message, "Could not start");
+        assert!(spawn.hint.unwrap().contains("Restart"));
+    }
+
+    #[test]
+    fn splits_dev_commands_like_a_shell() {
+        assert_eq!(
+            split_command(r#"  "/opt/my tools/python" -m 'brainshape.server'  "" "#).unwrap(),
+            ["/opt/my tools/python", "-m", "brainshape.server", ""]
+        );
+        if !cfg!(windows) {
+            assert_eq!(
+                split_command(r#"uv run my\ server "say \"hi\"""#).unwrap(),
+                ["uv", "run", "my server", "say \"hi\""]
+            );
+        }
+        assert!(split_command("uv run 'server").is_err());
+        assert!(split_command("   ").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,565 @@
+//! Backup and restore of app data.
+//!
+//! A backup is a zip with a `manifest.json` plus two trees:
+//!
+//! - `config/` — the backend's config dir (`~/.config/brainshape`: settings,
+//!   the embedded SurrealDB graph with agent memories)
+//! - `app-data/` — the Rust shell's own files (notifications)
+//!
+//! Notes are not included; they are plain files in the user's notes folder.
+//! `settings.json` holds API keys, so a backup is as sensitive as the config
+//! dir itself.
+//!
+//! The backend writes to its database while it runs, so `create_backup`
+//! stops it while the config dir is copied (`backend::while_stopped`) and
+//! then starts it again.
+//!
+//! Restoring while the backend has the database open isn't safe either, so
+//! `restore_backup` only validates and stages the archive. The staged data
+//! is applied by [`apply_pending_restore`] on the next launch, before the
+//! sidecar starts. If applying fails, the previous data is put back and the
+//! staged restore moved aside, so it isn't retried on every launch. The
+//! outcome is reported once as a notification by [`report_restore_outcome`],
+//! since notifications aren't set up yet when the restore runs.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+
+use crate::archive::safe_join;
+use crate::audit::{self, Access};
+use crate::backend;
+use crate::chaos;
+use crate::error::AppResult;
+use crate::notifications;
+use crate::paths;
+use crate::tasks;
+
+const MANIFEST: &str = "manifest.json";
+const FORMAT_VERSION: u32 = 1;
+
+/// Runtime files that must not be carried over to another session.
+const SKIPPED_FILES: &[&str] = &["port", "session.lock"];
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    format_version: u32,
+    app_version: String,
+    /// Unix timestamp in seconds.
+    created_at: u64,
+}
+
+/// What to do when restored data collides with existing data.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreStrategy {
+    /// Move existing data aside to `<dir>.pre-restore-<timestamp>`, then
+    /// restore everything.
+    Replace,
+    /// Only restore files that don't already exist.
+    KeepExisting,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PendingRestore {
+    strategy: RestoreStrategy,
+}
+
+/// How the last restore went, for [`report_restore_outcome`].
+#[derive(Serialize, Deserialize)]
+struct RestoreOutcome {
+    /// The error, if applying failed.
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct BackupInfo {
+    path: String,
+    files: usize,
+    bytes: u64,
+}
+
+/// The backend's config directory; matches `settings.py` on every platform.
+pub(crate) fn backend_config_dir(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(app.path().home_dir()?.join(".config").join("brainshape"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Reports `(done, total)` after each file; an error stops the operation.
+/// `total` is `0` when it isn't known up front.
+type Progress<'a> = &'a mut dyn FnMut(u64, u64) -> io::Result<()>;
+
+/// Add every file under `dir` to the zip under `prefix/`, skipping `skip`.
+fn add_tree(
+    zip: &mut zip::ZipWriter<File>,
+    dir: &Path,
+    prefix: &str,
+    skip: &Path,
+    files: &mut usize,
+    progress: Progress,
+) -> io::Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path == skip {
+            continue;
+        }
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let zip_name = format!("{prefix}/{name}");
+        if path.is_dir() {
+            add_tree(zip, &path, &zip_name, skip, files, progress)?;
+        } else if path.is_file() && !SKIPPED_FILES.contains(&name.as_ref()) {
+            zip.start_file(zip_name, SimpleFileOptions::default())?;
+            io::copy(&mut File::open(&path)?, zip)?;
+            *files += 1;
+            progress(*files as u64, 0)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_backup(
+    dest: &Path,
+    trees: &[(&str, &Path)],
+    skip: &Path,
+    progress: Progress,
+) -> io::Result<usize> {
+    let mut zip = zip::ZipWriter::new(File::create(dest)?);
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: now_secs(),
+    };
+    zip.start_file(MANIFEST, SimpleFileOptions::default())?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    let mut files = 0;
+    for (prefix, dir) in trees {
+        add_tree(&mut zip, dir, prefix, skip, &mut files, progress)?;
+    }
+    zip.finish()?;
+    Ok(files)
+}
+
+/// Check the manifest and extract a backup into `staging/<tree>/...`.
+fn stage_backup(archive: &Path, staging: &Path, progress: Progress) -> io::Result<usize> {
+    let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+    let manifest: Manifest = {
+        let mut text = String::new();
+        zip.by_name(MANIFEST)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Not a Brainshape backup"))?
+            .read_to_string(&mut text)?;
+        serde_json::from_str(&text)?
+    };
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Backup was made by a newer Brainshape ({}); update before restoring",
+                manifest.app_version
+            ),
+        ));
+    }
+
+    let mut files = 0;
+    let total = zip.len() as u64;
+    for i in 0..zip.len() {
+        progress(i as u64, total)?;
+        let mut file = zip.by_index(i)?;
+        if file.name() == MANIFEST || file.is_dir() {
+            continue;
+        }
+        let Some(target) = safe_join(staging, Path::new(file.name())) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Backup contains an unsafe path: {}", file.name()),
+            ));
+        };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut file, &mut File::create(target)?)?;
+        files += 1;
+    }
+    progress(total, total)?;
+    Ok(files)
+}
+
+/// Copy everything from `src` into `dest` according to `strategy`. The
+/// staged files stay intact, so a failed restore can be looked into.
+fn merge_tree(src: &Path, dest: &Path, strategy: RestoreStrategy) -> io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src)? {
+        let path = entry?.path();
+        let target = dest.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            merge_tree(&path, &target, strategy)?;
+        } else if strategy == RestoreStrategy::Replace || !target.exists() {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// A live directory that `replace` moved aside: `(live, aside)`, with no
+/// `aside` when there was nothing to move.
+type MovedAside = (PathBuf, Option<PathBuf>);
+
+/// Apply one staged tree onto its live directory. With `replace`, what it
+/// moves aside is pushed to `moved` before anything is copied, so a failure
+/// can be undone with [`undo_replace`].
+fn apply_tree(
+    staged: &Path,
+    live: &Path,
+    strategy: RestoreStrategy,
+    stamp: u64,
+    moved: &mut Vec<MovedAside>,
+) -> io::Result<()> {
+    if !staged.exists() {
+        return Ok(());
+    }
+    if strategy == RestoreStrategy::Replace {
+        if live.exists() {
+            let mut aside = live.as_os_str().to_owned();
+            aside.push(format!(".pre-restore-{stamp}"));
+            let aside = PathBuf::from(aside);
+            fs::rename(live, &aside)?;
+            eprintln!("[backup] Previous data kept at {}", aside.display());
+            moved.push((live.to_path_buf(), Some(aside)));
+        } else {
+            moved.push((live.to_path_buf(), None));
+        }
+    }
+    merge_tree(staged, live, strategy)
+}
+
+/// Put live directories moved aside by `apply_tree` back in place.
+fn undo_replace(moved: Vec<MovedAside>) {
+    for (live, aside) in moved.into_iter().rev() {
+        let _ = fs::remove_dir_all(&live);
+        if let Some(aside) = aside {
+            if let Err(e) = fs::rename(&aside, &live) {
+                eprintln!("[backup] Could not move {} back: {}", aside.display(), e);
+            }
+        }
+    }
+}
+
+fn outcome_path(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(app.path().app_cache_dir()?.join("restore-outcome.json"))
+}
+
+/// Staged restores live in the cache dir, outside both trees being replaced.
+fn pending_dir(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(app.path().app_cache_dir()?.join("pending-restore"))
+}
+
+/// Apply a restore staged by `restore_backup`. Must run before the sidecar
+/// starts, since the backend holds the database open.
+pub fn apply_pending_restore(app: &AppHandle) {
+    let (Ok(pending), Ok(config), Ok(app_data)) = (
+        pending_dir(app),
+        backend_config_dir(app),
+        app.path().app_data_dir(),
+    ) else {
+        return;
+    };
+    let Ok(text) = fs::read_to_string(pending.join("pending.json")) else {
+        return;
+    };
+    let strategy = match serde_json::from_str::<PendingRestore>(&text) {
+        Ok(p) => p.strategy,
+        Err(e) => {
+            eprintln!("[backup] Ignoring corrupt pending restore: {}", e);
+            let _ = fs::remove_dir_all(&pending);
+            return;
+        }
+    };
+
+    let stamp = now_secs();
+    let mut moved = Vec::new();
+    let result = apply_tree(
+        &pending.join("config"),
+        &config,
+        strategy,
+        stamp,
+        &mut moved,
+    )
+    .and_then(|()| {
+        apply_tree(
+            &pending.join("app-data"),
+            &app_data,
+            strategy,
+            stamp,
+            &mut moved,
+        )
+    });
+    let outcome = match result {
+        Ok(()) => {
+            eprintln!("[backup] Restore applied");
+            let _ = fs::remove_dir_all(&pending);
+            RestoreOutcome { error: None }
+        }
+        Err(e) => {
+            eprintln!(
+                "[backup] Restore failed, putting the previous data back: {}",
+                e
+            );
+            undo_replace(moved);
+            let mut failed = pending.as_os_str().to_owned();
+            failed.push(format!(".failed-{stamp}"));
+            let failed = PathBuf::from(failed);
+            match fs::rename(&pending, &failed) {
+                Ok(()) => eprintln!("[backup] Failed restore kept at {}", failed.display()),
+                Err(_) => {
+                    let _ = fs::remove_dir_all(&pending);
+                }
+            }
+            RestoreOutcome {
+                error: Some(e.to_string()),
+            }
+        }
+    };
+    if let Ok(path) = outcome_path(app) {
+        if let Ok(text) = serde_json::to_vec(&outcome) {
+            let _ = fs::write(path, text);
+        }
+    }
+}
+
+/// Notify about the restore applied at this launch, if there was one. Call
+/// once notifications are set up.
+pub fn report_restore_outcome(app: &AppHandle) {
+    let Ok(path) = outcome_path(app) else {
+        return;
+    };
+    let Ok(text) = fs::read_to_string(&path) else {
+        return;
+    };
+    let _ = fs::remove_file(&path);
+    let Ok(outcome) = serde_json::from_str::<RestoreOutcome>(&text) else {
+        return;
+    };
+    match outcome.error {
+        None => notifications::notify(app, notifications::Level::Info, "Backup restored", None),
+        Some(error) => notifications::notify(
+            app,
+            notifications::Level::Error,
+            "Backup could not be restored",
+            Some(format!(
+                "{error}. Your previous data is unchanged. To try again, restore the \
+                 backup file from Settings > Backup."
+            )),
+        ),
+    }
+}
+
+/// Write a backup of settings, the knowledge graph, and app data to `path`.
+#[tauri::command]
+pub async fn create_backup(app: AppHandle, path: String) -> AppResult<BackupInfo> {
+    chaos::check_disk(&app)?;
+    let config = backend_config_dir(&app)?;
+    let app_data = app.path().app_data_dir()?;
+    let dest = paths::authorize(&app, &path, true)?;
+    audit::record(&app, "create_backup", Access::Write, &dest);
+    let label = tasks::file_label(&dest);
+    let task_app = app.clone();
+    tasks::run(&task_app, "create_backup", label, move |task| {
+        // The recovery snapshots belong to this session, not the backup.
+        let skip = app_data.join("recovery");
+        let written = backend::while_stopped(&app, || {
+            write_backup(
+                &dest,
+                &[("config", &config), ("app-data", &app_data)],
+                &skip,
+                &mut |done, total| {
+                    task.progress(done, total);
+                    task.check()
+                },
+            )
+        })?;
+        let files = match written {
+            Ok(files) => files,
+            Err(e) => {
+                // Don't leave a truncated zip that looks like a backup.
+                let _ = fs::remove_file(&dest);
+                return Err(e.into());
+            }
+        };
+        let bytes = fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+        Ok(BackupInfo { path, files, bytes })
+    })
+    .await
+}
+
+/// Validate and stage a backup; it is applied on the next launch.
+/// Returns the number of files staged.
+#[tauri::command]
+pub async fn restore_backup(
+    app: AppHandle,
+    path: String,
+    strategy: RestoreStrategy,
+) -> AppResult<usize> {
+    chaos::check_disk(&app)?;
+    let source = paths::authorize(&app, &path, false)?;
+    audit::record(&app, "restore_backup", Access::Read, &source);
+    let pending = pending_dir(&app)?;
+    let label = tasks::file_label(&source);
+    tasks::run(&app, "restore_backup", label, move |task| {
+        let _ = fs::remove_dir_all(&pending);
+        fs::create_dir_all(&pending)?;
+        let mut progress = |done, total| {
+            task.progress(done, total);
+            task.check()
+        };
+        let staged = stage_backup(&source, &pending, &mut progress).and_then(|files| {
+            let marker = serde_json::to_vec(&PendingRestore { strategy })?;
+            fs::write(pending.join("pending.json"), marker)?;
+            Ok(files)
+        });
+        if staged.is_err() {
+            let _ = fs::remove_dir_all(&pending);
+        }
+        Ok(staged?)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("brainshape-backup-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn backup_round_trip() {
+        let dir = temp_dir("roundtrip");
+        let config = dir.join("config-live");
+        fs::create_dir_all(config.join("surrealdb")).unwrap();
+        fs::write(config.join("settings.json"), "{}").unwrap();
+        fs::write(config.join("surrealdb/data"), "graph").unwrap();
+        fs::write(config.join("port"), "52836").unwrap();
+
+        let archive = dir.join("backup.zip");
+        let files = write_backup(
+            &archive,
+            &[("config", &config)],
+            &dir.join("none"),
+            &mut |_, _| Ok(()),
+        )
+        .unwrap();
+        assert_eq!(files, 2);
+
+        let staging = dir.join("staging");
+        assert_eq!(
+            stage_backup(&archive, &staging, &mut |_, _| Ok(())).unwrap(),
+            2
+        );
+        assert_eq!(
+            fs::read_to_string(staging.join("config/surrealdb/data")).unwrap(),
+            "graph"
+        );
+        assert!(!staging.join("config/port").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_archives_without_manifest() {
+        let dir = temp_dir("nomanifest");
+        let archive = dir.join("other.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        zip.start_file("config/settings.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let err = stage_backup(&archive, &dir.join("staging"), &mut |_, _| Ok(())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn strategies_handle_conflicts() {
+        let dir = temp_dir("strategy");
+        let staged = dir.join("staged");
+        fs::create_dir_all(&staged).unwrap();
+        fs::write(staged.join("settings.json"), "restored").unwrap();
+        fs::write(staged.join("new.json"), "new").unwrap();
+
+        let live = dir.join("live");
+        fs::create_dir_all(&live).unwrap();
+        fs::write(live.join("settings.json"), "current").unwrap();
+        merge_tree(&staged, &live, RestoreStrategy::KeepExisting).unwrap();
+        assert_eq!(
+            fs::read_to_string(live.join("settings.json")).unwrap(),
+            "current"
+        );
+        assert_eq!(fs::read_to_string(live.join("new.json")).unwrap(), "new");
+
+        let mut moved = Vec::new();
+        apply_tree(&staged, &live, RestoreStrategy::Replace, 42, &mut moved).unwrap();
+        assert_eq!(
+            fs::read_to_string(live.join("settings.json")).unwrap(),
+            "restored"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("live.pre-restore-42/settings.json")).unwrap(),
+            "current"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn undoing_a_replace_keeps_both_sides() {
+        let dir = temp_dir("undo");
+        let staged = dir.join("staged");
+        fs::create_dir_all(&staged).unwrap();
+        fs::write(staged.join("settings.json"), "restored").unwrap();
+        let live = dir.join("live");
+        fs::create_dir_all(&live).unwrap();
+        fs::write(live.join("settings.json"), "current").unwrap();
+
+        let mut moved = Vec::new();
+        apply_tree(&staged, &live, RestoreStrategy::Replace, 7, &mut moved).unwrap();
+        apply_tree(
+            &staged,
+            &dir.join("fresh"),
+            RestoreStrategy::Replace,
+            7,
+            &mut moved,
+        )
+        .unwrap();
+        undo_replace(moved);
+
+        assert_eq!(
+            fs::read_to_string(live.join("settings.json")).unwrap(),
+            "current"
+        );
+        assert!(!dir.join("live.pre-restore-7").exists());
+        assert!(!dir.join("fresh").exists());
+        // The staged restore is still there to try again.
+        assert_eq!(
+            fs::read_to_string(staged.join("settings.json")).unwrap(),
+            "restored"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
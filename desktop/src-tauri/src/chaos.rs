@@ -0,0 +1,128 @@
+//! Failure injection for QA, enabled with the hidden `--chaos` flag.
+//!
+//! Lets testers exercise the frontend's failure paths from the devtools
+//! console instead of breaking their environment: crash the sidecar, slow
+//! down the mock backend, drop shell events, and fail writes as if the disk
+//! were full. The real backend's HTTP traffic doesn't pass through the
+//! shell, so response delays only apply with `--mock-backend`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::backend;
+use crate::error::{AppError, AppResult, LockExt};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Added before every mock backend response.
+    pub response_delay_ms: u64,
+    /// Fraction (0 to 1) of shell events that are silently dropped.
+    pub drop_event_rate: f64,
+    /// Fail backups, restores, archive imports, and recovery snapshots with
+    /// a storage-full error.
+    pub disk_full: bool,
+}
+
+pub type SharedChaos = Arc<Mutex<ChaosConfig>>;
+
+/// Only managed with `--chaos`.
+struct Chaos(SharedChaos);
+
+pub fn init(app: &AppHandle, enabled: bool) {
+    if enabled {
+        eprintln!("[chaos] Failure injection enabled");
+        app.manage(Chaos(SharedChaos::default()));
+    }
+}
+
+/// The live config, for components that run outside commands.
+pub fn shared(app: &AppHandle) -> Option<SharedChaos> {
+    app.try_state::<Chaos>().map(|chaos| chaos.0.clone())
+}
+
+fn config(app: &AppHandle) -> Option<ChaosConfig> {
+    app.try_state::<Chaos>()
+        .map(|chaos| chaos.0.lock_or_recover().clone())
+}
+
+/// Cheap pseudo-random number in `[0, 1)`; good enough to drop "some" events.
+fn roll() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    f64::from(nanos % 10_000) / 10_000.0
+}
+
+/// `app.emit`, except that chaos mode may drop the event.
+pub fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) -> tauri::Result<()> {
+    if let Some(config) = config(app) {
+        if config.drop_event_rate > 0.0 && roll() < config.drop_event_rate {
+            eprintln!("[chaos] Dropped {}", event);
+            return Ok(());
+        }
+    }
+    app.emit(event, payload)
+}
+
+/// Called before writes; fails with a storage-full error in chaos mode.
+pub fn check_disk(app: &AppHandle) -> AppResult<()> {
+    match config(app) {
+        Some(config) if config.disk_full => Err(AppError::StorageFull(
+            "No space left on device (simulated)".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn require(app: &AppHandle) -> AppResult<SharedChaos> {
+    shared(app).ok_or_else(|| {
+        AppError::Unavailable("Failure injection needs the app started with --chaos".into())
+    })
+}
+
+#[tauri::command]
+pub fn get_chaos(app: AppHandle) -> AppResult<ChaosConfig> {
+    Ok(require(&app)?.lock_or_recover().clone())
+}
+
+#[tauri::command]
+pub fn set_chaos(app: AppHandle, config: ChaosConfig) -> AppResult<ChaosConfig> {
+    if !(0.0..=1.0).contains(&config.drop_event_rate) {
+        return Err(AppError::InvalidInput(
+            "drop_event_rate must be between 0 and 1".into(),
+        ));
+    }
+    eprintln!("[chaos] {:?}", config);
+    *require(&app)?.lock_or_recover() = config.clone();
+    Ok(config)
+}
+
+/// Kill the sidecar as if it had crashed.
+#[tauri::command]
+pub fn chaos_crash_backend(app: AppHandle) -> AppResult<()> {
+    require(&app)?;
+    backend::crash(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_accepts_partial_json() {
+        let config: ChaosConfig = serde_json::from_str(r#"{"disk_full": true}"#).unwrap();
+        assert_eq!(
+            config,
+            ChaosConfig {
+                disk_full: true,
+                ..Default::default()
+            }
+        );
+        assert!((0.0..1.0).contains(&roll()));
+    }
+}
@@ -0,0 +1,161 @@
+//! Command-line arguments for the desktop binary; see [`USAGE`].
+//!
+//! Unknown flags are reported and ignored, since OS launchers add their own
+//! (e.g. `-psn_*` on older macOS). URLs are left to the deep-link plugin.
+
+use std::ffi::OsString;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
+
+use tauri::Url;
+
+pub const USAGE: &str = "\
+Usage: brainshape [OPTIONS] [PATH...]
+
+  PATH, --open <PATH>    Import a note, folder, or archive
+  --backend-url <URL>    Use a backend already running on this machine
+                         instead of starting the bundled one
+  --mock-backend         Serve canned fixture data instead of a real
+                         backend (frontend development and demos)
+  --verbose              Debug logging from the backend
+  -h, --help             Print this help";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Cli {
+    pub open: Vec<PathBuf>,
+    /// Port of an existing backend from `--backend-url`.
+    pub backend_port: Option<u16>,
+    pub mock_backend: bool,
+    /// Hidden `--chaos`: enable failure injection for QA.
+    pub chaos: bool,
+    /// Hidden `--allow-unsigned-backend`: run a sidecar that fails the
+    /// signature check (developers testing their own backend builds).
+    pub allow_unsigned_backend: bool,
+    pub verbose: bool,
+    pub help: bool,
+    /// Unrecognised flags.
+    pub ignored: Vec<String>,
+}
+
+/// The backend serves notes and API keys without authentication, so only
+/// accept one listening on the loopback interface. Also used for saved
+/// connections. Only the port is kept: health checks and the frontend
+/// always reach the backend at `127.0.0.1`, so IPv6 loopback is refused
+/// rather than accepted and never reached.
+pub(crate) fn loopback_port(value: &str) -> Result<u16, String> {
+    let url: Url = value
+        .parse()
+        .map_err(|e| format!("Invalid backend URL {value}: {e}"))?;
+    let host = url.host_str().unwrap_or_default();
+    if host
+        .trim_matches(['[', ']'])
+        .parse::<Ipv6Addr>()
+        .is_ok_and(|ip| ip.is_loopback())
+    {
+        return Err(format!(
+            "IPv6 backend URLs aren't supported; run the backend on 127.0.0.1, got {value}"
+        ));
+    }
+    let loopback = host == "localhost" || host.parse::<Ipv4Addr>().is_ok_and(|ip| ip.is_loopback());
+    if url.scheme() != "http" || !loopback {
+        return Err(format!(
+            "The backend URL must be an http:// address on this machine, got {value}"
+        ));
+    }
+    url.port_or_known_default()
+        .ok_or_else(|| format!("Backend URL has no port: {value}"))
+}
+
+impl Cli {
+    /// Parse arguments, excluding the program name.
+    pub fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Self, String> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let text = arg.to_string_lossy().into_owned();
+            let (flag, inline) = match text.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(OsString::from(value)))
+                }
+                _ => (text.clone(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{flag} needs a value"))
+            };
+            match flag.as_str() {
+                "--open" => cli.open.push(value()?.into()),
+                "--backend-url" => {
+                    let port = loopback_port(&value()?.to_string_lossy())
+                        .map_err(|e| format!("--backend-url: {e}"))?;
+                    cli.backend_port = Some(port)
+                }
+                "--mock-backend" => cli.mock_backend = true,
+                "--chaos" => cli.chaos = true,
+                "--allow-unsigned-backend" => cli.allow_unsigned_backend = true,
+                "--verbose" => cli.verbose = true,
+                "-h" | "--help" => cli.help = true,
+                _ if text.starts_with('-') => cli.ignored.push(text),
+                _ if text.contains("://") => {}
+                _ => cli.open.push(arg.into()),
+            }
+        }
+        if cli.mock_backend && cli.backend_port.is_some() {
+            return Err("--mock-backend and --backend-url can't be combined".into());
+        }
+        Ok(cli)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, String> {
+        Cli::parse(args.iter().map(Into::into))
+    }
+
+    #[test]
+    fn parses_options_and_paths() {
+        let cli = parse(&[
+            "notes/a.md",
+            "--open",
+            "vault.zip",
+            "--backend-url=http://127.0.0.1:8765",
+            "--verbose",
+            "-psn_0_1234",
+            "brainshape://open?path=a.md",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli,
+            Cli {
+                open: vec!["notes/a.md".into(), "vault.zip".into()],
+                backend_port: Some(8765),
+                mock_backend: false,
+                chaos: false,
+                allow_unsigned_backend: false,
+                verbose: true,
+                help: false,
+                ignored: vec!["-psn_0_1234".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn backend_url_must_be_loopback() {
+        assert_eq!(
+            parse(&["--backend-url", "http://localhost"])
+                .unwrap()
+                .backend_port,
+            Some(80)
+        );
+        assert!(parse(&["--backend-url", "http://[::1]:9000"]).is_err());
+        assert!(parse(&["--backend-url", "http://192.168.1.5:8000"]).is_err());
+        assert!(parse(&["--backend-url", "https://127.0.0.1:8000"]).is_err());
+        assert!(parse(&["--backend-url"]).is_err());
+        assert!(parse(&["--mock-backend", "--backend-url", "http://localhost:1"]).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Saved backend connections, and switching between them at runtime.
+//!
+//! The backend has no authentication, so a saved connection is a named
+//! loopback URL, held to the same rule as `--backend-url`. There are no
+//! auth or TLS options to store; a backend on another machine is reached
+//! through the connection's SSH tunnel (see `tunnel`), which forwards the
+//! URL's port to it. The built-in backend (no name) is the bundled sidecar,
+//! or the developer's server in debug builds. The default connection is
+//! used at launch unless the command line picks a backend.
+//!
+//! Switching stops the sidecar if it runs, points the shell at the new
+//! backend (`backend::switch`), and emits `backend-switched`, on which the
+//! frontend reloads so nothing from the old backend lingers. Only once the
+//! shell has moved over is the old tunnel replaced with the new one's, so
+//! a refused switch leaves the current connection working.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::backend::{self, Connection};
+use crate::cli;
+use crate::error::{AppError, AppResult};
+use crate::settings;
+use crate::sidecar;
+use crate::tunnel::{self, SshTunnel};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedConnection {
+    pub name: String,
+    pub url: String,
+    /// Reach the backend through this tunnel, forwarded to the URL's port.
+    #[serde(default)]
+    pub tunnel: Option<SshTunnel>,
+}
+
+#[derive(Serialize)]
+pub struct Connections {
+    saved: Vec<SavedConnection>,
+    default: Option<String>,
+    active: Connection,
+}
+
+fn port_of(url: &str) -> AppResult<u16> {
+    cli::loopback_port(url).map_err(AppError::InvalidInput)
+}
+
+/// The saved connection called `name`, with its port.
+fn find<'a>(saved: &'a [SavedConnection], name: &str) -> AppResult<(&'a SavedConnection, u16)> {
+    let connection = saved
+        .iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| AppError::NotFound(format!("No saved connection named {name}")))?;
+    Ok((connection, port_of(&connection.url)?))
+}
+
+/// Use the default connection at launch, if one is set and still valid.
+/// `false` leaves the choice of backend to the caller.
+pub fn use_default(app: &AppHandle, verbose: bool, allow_unsigned: bool) -> bool {
+    let settings = settings::get(app);
+    let Some(name) = settings.default_connection else {
+        return false;
+    };
+    let (connection, port) = match find(&settings.connections, &name) {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("[connections] Ignoring default connection: {}", e);
+            return false;
+        }
+    };
+    eprintln!("[backend] Using saved connection {} on port {}", name, port);
+    if let Some(tunnel) = connection.tunnel.clone() {
+        tunnel::open(app, name.clone(), tunnel, port);
+    }
+    backend::use_saved(app, name, port, verbose, allow_unsigned);
+    true
+}
+
+/// Add `connection`, or replace the saved one with the same name.
+fn upsert(saved: &mut Vec<SavedConnection>, connection: SavedConnection) {
+    match saved.iter_mut().find(|c| c.name == connection.name) {
+        Some(existing) => *existing = connection,
+        None => saved.push(connection),
+    }
+}
+
+fn builtin() -> Connection {
+    if cfg!(debug_assertions) {
+        Connection::External {
+            name: None,
+            port: backend::DEFAULT_PORT,
+        }
+    } else {
+        Connection::Sidecar {
+            port: backend::DEFAULT_PORT,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn list_connections(app: AppHandle) -> Connections {
+    let settings = settings::get(&app);
+    Connections {
+        saved: settings.connections,
+        default: settings.default_connection,
+        active: backend::connection(&app),
+    }
+}
+
+/// Save a connection, replacing one with the same name.
+#[tauri::command]
+pub fn save_connection(
+    app: AppHandle,
+    name: String,
+    url: String,
+    tunnel: Option<SshTunnel>,
+) -> AppResult<Connections> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Give the connection a name".into()));
+    }
+    port_of(&url)?;
+    if let Some(tunnel) = &tunnel {
+        tunnel.validate()?;
+    }
+    settings::update(&app, |s| {
+        upsert(&mut s.connections, SavedConnection { name, url, tunnel })
+    })?;
+    Ok(list_connections(app))
+}
+
+/// Forget a saved connection. The active backend stays until switched.
+#[tauri::command]
+pub fn remove_connection(app: AppHandle, name: String) -> AppResult<Connections> {
+    settings::update(&app, |s| {
+        s.connections.retain(|c| c.name != name);
+        if s.default_connection.as_deref() == Some(name.as_str()) {
+            s.default_connection = None;
+        }
+    })?;
+    Ok(list_connections(app))
+}
+
+/// Whether a backend answers at `url`.
+#[tauri::command]
+pub async fn test_connection(url: String) -> AppResult<bool> {
+    let port = port_of(&url)?;
+    Ok(tauri::async_runtime::spawn_blocking(move || sidecar::is_healthy(port)).await?)
+}
+
+/// Use the saved connection `name` at launch, or the built-in backend with
+/// `None`.
+#[tauri::command]
+pub fn set_default_connection(app: AppHandle, name: Option<String>) -> AppResult<Connections> {
+    if let Some(name) = &name {
+        find(&settings::get(&app).connections, name)?;
+    }
+    settings::update(&app, |s| s.default_connection = name)?;
+    Ok(list_connections(app))
+}
+
+/// Switch to the saved connection `name`, or back to the built-in backend
+/// with `None`.
+#[tauri::command]
+pub async fn switch_connection(app: AppHandle, name: Option<String>) -> AppResult<()> {
+    let (connection, tunnel) = match name {
+        Some(name) => {
+            let settings = settings::get(&app);
+            let (saved, port) = find(&settings.connections, &name)?;
+            let tunnel = saved.tunnel.clone().map(|t| (name.clone(), t, port));
+            let connection = Connection::External {
+                name: Some(name),
+                port,
+            };
+            (connection, tunnel)
+        }
+        None => (builtin(), None),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = backend::switch(&app, connection.clone());
+        // Leave the old tunnel alone unless the switch went through; a
+        // refused switch keeps the current connection working.
+        if backend::connection(&app) == connection {
+            match tunnel {
+                Some((name, tunnel, port)) => tunnel::open(&app, name, tunnel, port),
+                None => tunnel::close(&app),
+            }
+        }
+        result
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_by_name_and_only_loopback() {
+        let mut saved = Vec::new();
+        let lab = |url: &str| SavedConnection {
+            name: "Lab".into(),
+            url: url.into(),
+            tunnel: None,
+        };
+        upsert(&mut saved, lab("http://127.0.0.1:9000"));
+        upsert(&mut saved, lab("http://localhost:9100"));
+        assert_eq!(saved.len(), 1);
+        assert_eq!(find(&saved, "Lab").unwrap().1, 9100);
+        assert_eq!(find(&saved, "Other").unwrap_err().code(), "not_found");
+
+        upsert(&mut saved, lab("http://10.0.0.5:9000"));
+        assert_eq!(find(&saved, "Lab").unwrap_err().code(), "invalid_input");
+    }
+}
@@ -0,0 +1,135 @@
+//! `brainshape://` links.
+//!
+//! Supported links:
+//!
+//! - `brainshape://open?path=Folder/Note.md` — open a note, by its path
+//!   relative to the notes folder
+//!
+//! Links are parsed and validated here and emitted to the frontend as
+//! `deep-link` events. Links that arrive before the frontend can open notes
+//! (including the one that launched the app) are queued until
+//! `take_pending_deep_links`.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::chaos;
+use crate::error::LockExt;
+use crate::paths;
+
+pub const SCHEME: &str = "brainshape";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeepLink {
+    Open { path: String },
+}
+
+impl DeepLink {
+    pub fn parse(url: &Url) -> Result<Self, String> {
+        if url.scheme() != SCHEME {
+            return Err(format!("Not a {SCHEME}:// link: {url}"));
+        }
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.into_owned())
+        };
+        match url.host_str() {
+            Some("open") => {
+                let path = param("path").ok_or("Missing path")?;
+                // Note paths are relative to the notes folder; never let a
+                // link name anything outside it.
+                if !paths::is_plain_relative(&path) {
+                    return Err(format!("Invalid note path: {path}"));
+                }
+                Ok(Self::Open { path })
+            }
+            other => Err(format!("Unknown link action: {}", other.unwrap_or(""))),
+        }
+    }
+}
+
+#[derive(Default)]
+struct LinkQueue {
+    ready: bool,
+    pending: Vec<DeepLink>,
+}
+
+fn handle_urls(app: &AppHandle, urls: Vec<Url>) {
+    let Some(queue) = app.try_state::<Mutex<LinkQueue>>() else {
+        return;
+    };
+    let mut queue = queue.lock_or_recover();
+    for url in urls {
+        match DeepLink::parse(&url) {
+            Ok(link) if queue.ready => {
+                let _ = chaos::emit(app, "deep-link", link);
+            }
+            Ok(link) => queue.pending.push(link),
+            Err(e) => eprintln!("[deep-link] Ignoring {}: {}", url, e),
+        }
+    }
+}
+
+/// Queue the link that launched the app, if any, and listen for new ones.
+pub fn init(app: &AppHandle) {
+    app.manage(Mutex::new(LinkQueue::default()));
+
+    // Installers register the scheme; in dev builds register it here so
+    // links can be tested without bundling.
+    #[cfg(any(windows, target_os = "linux"))]
+    if cfg!(debug_assertions) {
+        if let Err(e) = app.deep_link().register_all() {
+            eprintln!("[deep-link] Failed to register {SCHEME}://: {}", e);
+        }
+    }
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        handle_urls(app, urls);
+    }
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle_urls(&handle, event.urls());
+    });
+}
+
+/// Return links queued during startup; later links arrive as `deep-link` events.
+#[tauri::command]
+pub fn take_pending_deep_links(app: AppHandle) -> Vec<DeepLink> {
+    let queue = app.state::<Mutex<LinkQueue>>();
+    let mut queue = queue.lock_or_recover();
+    queue.ready = true;
+    std::mem::take(&mut queue.pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Result<DeepLink, String> {
+        DeepLink::parse(&url.parse().unwrap())
+    }
+
+    #[test]
+    fn parses_open_links() {
+        assert_eq!(
+            parse("brainshape://open?path=Projects%2FPlan%20A.md"),
+            Ok(DeepLink::Open {
+                path: "Projects/Plan A.md".into()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unsafe_or_unknown_links() {
+        assert!(parse("brainshape://open?path=..%2Fsecrets.md").is_err());
+        assert!(parse("brainshape://open?path=%2Fetc%2Fpasswd").is_err());
+        assert!(parse("brainshape://open").is_err());
+        assert!(parse("brainshape://job/1234").is_err());
+        assert!(parse("https://open?path=a.md").is_err());
+    }
+}
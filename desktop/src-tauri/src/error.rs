@@ -0,0 +1,172 @@
+//! Error type returned by every Tauri command.
+//!
+//! Serialized as `{ code, message, hint }` so the frontend can show the
+//! message, offer the hint as the next step, and branch on `code` without
+//! parsing text.
+
+use std::fmt;
+use std::io;
+use std::sync::{Mutex, MutexGuard};
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+#[derive(Debug)]
+pub enum AppError {
+    /// Bad arguments from the caller, e.g. a malformed path or shortcut.
+    InvalidInput(String),
+    NotFound(String),
+    /// The path or action is outside what the app is allowed to touch.
+    PermissionDenied(String),
+    /// Out of disk space or over the temporary workspace quota.
+    StorageFull(String),
+    /// A feature that is disabled or not supported on this system.
+    Unavailable(String),
+    /// The user cancelled the task; not worth showing as an error.
+    Cancelled(String),
+    Io(io::Error),
+    /// Anything else; a bug or an unexpected platform failure.
+    Internal(String),
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidInput(_) => "invalid_input",
+            Self::NotFound(_) => "not_found",
+            Self::PermissionDenied(_) => "permission_denied",
+            Self::StorageFull(_) => "storage_full",
+            Self::Unavailable(_) => "unavailable",
+            Self::Cancelled(_) => "cancelled",
+            Self::Io(_) => "io",
+            Self::Internal(_) => "internal",
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::NotFound(_) => Some("Check that the file still exists and try again."),
+            Self::PermissionDenied(_) => {
+                Some("Only files inside the notes folder or picked in a dialog can be used.")
+            }
+            Self::StorageFull(_) => Some("Free up disk space, or import a smaller archive."),
+            Self::Io(_) => {
+                Some("Check that the file isn't open elsewhere and the disk is writable.")
+            }
+            Self::Internal(_) => Some("Restart Brainshape; if it keeps happening, report a bug."),
+            Self::InvalidInput(_) | Self::Unavailable(_) | Self::Cancelled(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput(msg)
+            | Self::NotFound(msg)
+            | Self::PermissionDenied(msg)
+            | Self::StorageFull(msg)
+            | Self::Unavailable(msg)
+            | Self::Cancelled(msg)
+            | Self::Internal(msg) => f.write_str(msg),
+            Self::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AppError", 3)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.serialize_field("hint", &self.hint())?;
+        s.end()
+    }
+}
+
+/// Keep the error kinds the frontend can act on; everything else is `Io`.
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => Self::NotFound(e.to_string()),
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied(e.to_string()),
+            io::ErrorKind::StorageFull => Self::StorageFull(e.to_string()),
+            // Raised by `Task::check`; std's read and copy loops retry real EINTRs.
+            io::ErrorKind::Interrupted => Self::Cancelled(e.to_string()),
+            io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => {
+                Self::InvalidInput(e.to_string())
+            }
+            _ => Self::Io(e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(e: zip::result::ZipError) -> Self {
+        Self::InvalidInput(format!("Not a valid archive: {e}"))
+    }
+}
+
+/// Locking that survives a poisoned mutex. The guarded state here is always
+/// left consistent between statements, so a panic elsewhere shouldn't take
+/// every later command down with it.
+pub trait LockExt<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> LockExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_message_and_hint() {
+        let err = AppError::from(io::Error::new(io::ErrorKind::StorageFull, "disk full"));
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "code": "storage_full",
+                "message": "disk full",
+                "hint": "Free up disk space, or import a smaller archive.",
+            })
+        );
+        let err = AppError::InvalidInput("bad".into());
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["hint"],
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn recovers_poisoned_lock() {
+        let lock = std::sync::Arc::new(Mutex::new(1));
+        let poisoner = lock.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert!(lock.is_poisoned());
+        assert_eq!(*lock.lock_or_recover(), 1);
+    }
+}
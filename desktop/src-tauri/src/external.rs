@@ -0,0 +1,38 @@
+//! Opening notes outside the app: in the default application, or revealed
+//! in Finder / Explorer / the file manager.
+//!
+//! Commands take note paths relative to the notes folder, the same paths
+//! the backend API uses, and refuse anything that resolves outside it (see
+//! `paths.rs`). The webview can't name the application to open with: that
+//! would let it launch any program with a note as its argument.
+
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::audit::{self, Access};
+use crate::error::{AppError, AppResult};
+use crate::paths;
+
+fn opener_error(e: tauri_plugin_opener::Error) -> AppError {
+    AppError::Unavailable(format!("Could not open: {e}"))
+}
+
+/// Open a note or folder in the default application.
+#[tauri::command]
+pub fn open_note_externally(app: AppHandle, path: String) -> AppResult<()> {
+    let target = paths::resolve_note(&app, &path)?;
+    audit::record(&app, "open_note_externally", Access::Open, &target);
+    app.opener()
+        .open_path(target.to_string_lossy(), None::<&str>)
+        .map_err(opener_error)
+}
+
+/// Show a note or folder selected in the platform file manager.
+#[tauri::command]
+pub fn reveal_note(app: AppHandle, path: String) -> AppResult<()> {
+    let target = paths::resolve_note(&app, &path)?;
+    audit::record(&app, "reveal_note", Access::Open, &target);
+    app.opener()
+        .reveal_item_in_dir(target)
+        .map_err(opener_error)
+}
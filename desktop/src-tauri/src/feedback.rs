@@ -0,0 +1,73 @@
+//! Problem reports, filed as GitHub issues.
+//!
+//! There is no support endpoint: `report_problem` opens the new-issue form
+//! in the browser, prefilled with the user's message and the app version,
+//! OS, and sandbox. The user sees the whole report there before posting,
+//! and nothing else (logs, notes, paths) is attached.
+
+use tauri::{AppHandle, Url};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::error::{AppError, AppResult};
+use crate::sandbox;
+
+const NEW_ISSUE_URL: &str = "https://github.com/daniel-mallett/brainshape/issues/new";
+
+/// Longest issue title taken from the message's first line.
+const TITLE_CHARS: usize = 80;
+
+/// The prefilled new-issue URL for `message`, with `details` appended.
+fn issue_url(message: &str, details: &str) -> AppResult<Url> {
+    let message = message.trim();
+    let first_line = message.lines().next().unwrap_or_default();
+    let mut title: String = first_line.chars().take(TITLE_CHARS).collect();
+    if title.len() < first_line.len() {
+        title.push('…');
+    }
+    let body = format!("{message}\n\n---\n{details}");
+    Url::parse_with_params(NEW_ISSUE_URL, [("title", title), ("body", body)])
+        .map_err(|e| AppError::Internal(format!("Bad issue URL: {e}")))
+}
+
+/// Open a prefilled issue for `message` in the browser.
+#[tauri::command]
+pub fn report_problem(app: AppHandle, message: String) -> AppResult<()> {
+    if message.trim().is_empty() {
+        return Err(AppError::InvalidInput("Describe the problem first".into()));
+    }
+    let details = format!(
+        "Brainshape {} on {} {} (sandbox: {:?})",
+        app.package_info().version,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        sandbox::detect()
+    );
+    let url = issue_url(&message, &details)?;
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| AppError::Unavailable(format!("Could not open the browser: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefills_title_and_body() {
+        let url = issue_url("  Sync hangs\nafter import & restart ", "Brainshape 0.1.0").unwrap();
+        let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert_eq!(params[0], ("title".into(), "Sync hangs".into()));
+        assert_eq!(
+            params[1],
+            (
+                "body".into(),
+                "Sync hangs\nafter import & restart\n\n---\nBrainshape 0.1.0".into()
+            )
+        );
+
+        let long = "x".repeat(200);
+        let url = issue_url(&long, "").unwrap();
+        let title = url.query_pairs().next().unwrap().1;
+        assert_eq!(title.chars().count(), TITLE_CHARS + 1);
+    }
+}
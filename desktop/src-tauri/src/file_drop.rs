@@ -0,0 +1,325 @@
+//! Validation of files dropped onto the window or opened with the app.
+//!
+//! Drop and "open with" events only carry raw paths. Here each path is
+//! checked (exists, supported type, size), duplicates and paths inside
+//! another dropped folder are removed, and loose notes are copied into one
+//! staging directory so the frontend can import everything through
+//! `/import/vault`. The result is emitted as a single `files-dropped` event.
+//!
+//! Files opened at launch arrive before the frontend is listening or the
+//! backend is up, so they are queued until `ready_for_opened_files`.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::archive;
+use crate::audit::{self, Access};
+use crate::chaos;
+use crate::error::{AppResult, LockExt};
+use crate::flags;
+use crate::notifications;
+use crate::paths;
+use crate::tasks;
+use crate::workspace;
+
+/// Larger markdown files are almost certainly not notes.
+const MAX_NOTE_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropKind {
+    /// A single markdown file.
+    Note,
+    /// A folder containing markdown files.
+    Folder,
+    /// A `.zip` or `.tar.gz` export, for `import_archive`.
+    Archive,
+    /// A folder with no markdown files.
+    Empty,
+    TooLarge,
+    Unsupported,
+    Missing,
+    /// A note with the same file name as one dropped alongside it.
+    Duplicate,
+}
+
+impl DropKind {
+    pub fn accepted(&self) -> bool {
+        matches!(self, Self::Note | Self::Folder | Self::Archive)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DroppedItem {
+    path: String,
+    kind: DropKind,
+    bytes: u64,
+    markdown_files: usize,
+}
+
+/// Payload of the `files-dropped` event.
+#[derive(Clone, Debug, Serialize)]
+struct FilesDropped {
+    items: Vec<DroppedItem>,
+    /// Staging directory holding copies of the dropped notes. Import it, then
+    /// pass it to `release_workspace_dir`.
+    notes_dir: Option<String>,
+}
+
+fn classify_one(path: &Path, max_archive_bytes: u64) -> (DropKind, u64, usize) {
+    let Ok(meta) = fs::metadata(path) else {
+        return (DropKind::Missing, 0, 0);
+    };
+    if meta.is_dir() {
+        return match archive::scan(path) {
+            Ok((_, 0)) | Err(_) => (DropKind::Empty, 0, 0),
+            Ok((_, markdown)) => (DropKind::Folder, 0, markdown),
+        };
+    }
+    let bytes = meta.len();
+    let is_markdown = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+    if is_markdown {
+        let kind = if bytes > MAX_NOTE_BYTES {
+            DropKind::TooLarge
+        } else {
+            DropKind::Note
+        };
+        (kind, bytes, 1)
+    } else if archive::is_archive(path) {
+        let kind = if bytes > max_archive_bytes {
+            DropKind::TooLarge
+        } else {
+            DropKind::Archive
+        };
+        (kind, bytes, 0)
+    } else {
+        (DropKind::Unsupported, bytes, 0)
+    }
+}
+
+/// Classify dropped paths, dropping exact duplicates and anything inside
+/// another dropped folder.
+fn classify(paths: &[PathBuf], max_archive_bytes: u64) -> Vec<DroppedItem> {
+    let mut seen = HashSet::new();
+    let mut resolved: Vec<PathBuf> = paths
+        .iter()
+        .map(|p| p.canonicalize().unwrap_or_else(|_| p.clone()))
+        .filter(|p| seen.insert(p.clone()))
+        .collect();
+    // Parents sort before their children, so folders are known first.
+    resolved.sort();
+
+    let mut folders: Vec<PathBuf> = Vec::new();
+    let mut names = HashSet::new();
+    let mut items = Vec::new();
+    for path in resolved {
+        if folders.iter().any(|folder| path.starts_with(folder)) {
+            continue;
+        }
+        let (mut kind, bytes, markdown_files) = classify_one(&path, max_archive_bytes);
+        match kind {
+            DropKind::Folder => folders.push(path.clone()),
+            DropKind::Note if !names.insert(path.file_name().map(|n| n.to_owned())) => {
+                kind = DropKind::Duplicate;
+            }
+            _ => {}
+        }
+        items.push(DroppedItem {
+            path: path.to_string_lossy().into_owned(),
+            kind,
+            bytes,
+            markdown_files,
+        });
+    }
+    items
+}
+
+/// Copy accepted loose notes into `dest`.
+fn stage_notes(items: &[DroppedItem], dest: &Path) -> io::Result<usize> {
+    let mut staged = 0;
+    for item in items.iter().filter(|i| i.kind == DropKind::Note) {
+        let source = Path::new(&item.path);
+        if let Some(name) = source.file_name() {
+            fs::copy(source, dest.join(name))?;
+            staged += 1;
+        }
+    }
+    Ok(staged)
+}
+
+fn process(app: &AppHandle, paths: &[PathBuf]) -> AppResult<FilesDropped> {
+    let workspace = workspace::get(app)?;
+    let items = classify(paths, workspace.remaining());
+    let mut notes_dir = None;
+    if items.iter().any(|i| i.kind == DropKind::Note) {
+        let dir = workspace.allocate("drop")?;
+        if let Err(e) = stage_notes(&items, &dir) {
+            let _ = workspace.release(&dir);
+            return Err(e.into());
+        }
+        notes_dir = Some(dir.to_string_lossy().into_owned());
+    }
+    Ok(FilesDropped { items, notes_dir })
+}
+
+/// Handle a window drop event off the event loop; scanning a large folder
+/// can take a while.
+pub fn handle_drop(app: &AppHandle, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+    if !flags::is_enabled(app, "drop-import") {
+        eprintln!("[drop] Ignoring drop: drop-import is disabled");
+        return;
+    }
+    let app = app.clone();
+    let label = match paths.as_slice() {
+        [only] => tasks::file_label(only),
+        _ => format!("{} items", paths.len()),
+    };
+    tauri::async_runtime::spawn(async move {
+        let handle = app.clone();
+        let staged = tasks::run(&app, "stage_drop", label, move |_| process(&handle, &paths)).await;
+        match staged {
+            Ok(dropped) => {
+                // Dropped archives come back through `import_archive`.
+                for item in &dropped.items {
+                    if item.kind.accepted() {
+                        audit::record(&app, "drop", Access::Read, Path::new(&item.path));
+                    }
+                    if item.kind == DropKind::Archive {
+                        paths::grant(&app, Path::new(&item.path));
+                    }
+                }
+                let accepted = dropped.items.iter().filter(|i| i.kind.accepted()).count();
+                eprintln!(
+                    "[drop] {} of {} dropped item(s) accepted",
+                    accepted,
+                    dropped.items.len()
+                );
+                let _ = chaos::emit(&app, "files-dropped", dropped);
+            }
+            Err(e) => {
+                eprintln!("[drop] Failed to stage dropped files: {}", e);
+                notifications::notify(
+                    &app,
+                    notifications::Level::Error,
+                    "Dropped files couldn't be added",
+                    Some(e.to_string()),
+                );
+            }
+        }
+    });
+}
+
+/// Paths opened with the app before the frontend could import them.
+#[derive(Default)]
+pub struct OpenQueue {
+    ready: bool,
+    pending: Vec<PathBuf>,
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(Mutex::new(OpenQueue::default()));
+}
+
+/// Route files opened with the app into the import flow, queueing them until
+/// the frontend is ready.
+pub fn open_paths(app: &AppHandle, paths: Vec<PathBuf>) {
+    let Some(queue) = app.try_state::<Mutex<OpenQueue>>() else {
+        return;
+    };
+    let mut queue = queue.lock_or_recover();
+    if queue.ready {
+        handle_drop(app, paths);
+    } else {
+        queue.pending.extend(paths);
+    }
+}
+
+/// Called by the frontend once it listens for `files-dropped` and the
+/// backend is reachable; flushes files opened during startup.
+#[tauri::command]
+pub fn ready_for_opened_files(app: AppHandle) {
+    let pending = {
+        let queue = app.state::<Mutex<OpenQueue>>();
+        let mut queue = queue.lock_or_recover();
+        queue.ready = true;
+        std::mem::take(&mut queue.pending)
+    };
+    handle_drop(&app, pending);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_and_deduplicates() {
+        let dir = std::env::temp_dir().join(format!("brainshape-drop-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("vault/sub")).unwrap();
+        // Compare against canonical paths (e.g. /var vs /private/var on macOS).
+        let dir = dir.canonicalize().unwrap();
+        fs::create_dir_all(dir.join("images")).unwrap();
+        fs::create_dir_all(dir.join("other")).unwrap();
+        fs::write(dir.join("vault/sub/a.md"), "# A").unwrap();
+        fs::write(dir.join("images/pic.png"), "png").unwrap();
+        fs::write(dir.join("note.md"), "# Note").unwrap();
+        fs::write(dir.join("other/note.md"), "# Other").unwrap();
+        fs::write(dir.join("export.zip"), "zip").unwrap();
+        fs::write(dir.join("data.csv"), "a,b").unwrap();
+
+        let items = classify(
+            &[
+                dir.join("vault"),
+                dir.join("vault/sub/a.md"),
+                dir.join("images"),
+                dir.join("note.md"),
+                dir.join("./note.md"),
+                dir.join("other/note.md"),
+                dir.join("export.zip"),
+                dir.join("data.csv"),
+                dir.join("gone.md"),
+            ],
+            u64::MAX,
+        );
+        let kinds: Vec<_> = items.iter().map(|i| i.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            [
+                DropKind::Unsupported,
+                DropKind::Archive,
+                DropKind::Missing,
+                DropKind::Empty,
+                DropKind::Note,
+                DropKind::Duplicate,
+                DropKind::Folder,
+            ]
+        );
+        assert_eq!(items[6].markdown_files, 1);
+
+        let staging = dir.join("staging");
+        fs::create_dir_all(&staging).unwrap();
+        assert_eq!(stage_notes(&items, &staging).unwrap(), 1);
+        assert!(staging.join("note.md").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_archives_over_quota() {
+        let path = std::env::temp_dir().join(format!("brainshape-drop-{}.zip", std::process::id()));
+        fs::write(&path, "0123456789").unwrap();
+        assert_eq!(classify_one(&path, 4).0, DropKind::TooLarge);
+        assert_eq!(classify_one(&path, 100).0, DropKind::Archive);
+        fs::remove_file(&path).unwrap();
+    }
+}
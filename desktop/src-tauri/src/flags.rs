@@ -0,0 +1,162 @@
+//! Runtime feature flags, so experimental subsystems can ship dark and be
+//! turned on per user.
+//!
+//! Defaults are compiled into [`FLAGS`]; a user's overrides live in the
+//! desktop settings, and `BRAINSHAPE_FLAGS` (e.g. `automation,-drop-import`)
+//! overrides both for one launch, which is how CI turns flags on. Changing
+//! a flag emits `feature-flags-changed` with the full list so the frontend
+//! can re-render without polling.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::{AppError, AppResult};
+use crate::{chaos, settings};
+
+pub struct FlagDef {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub default: bool,
+}
+
+pub const FLAGS: &[FlagDef] = &[
+    FlagDef {
+        id: "drop-import",
+        description: "Offer to import files dropped onto the window",
+        default: true,
+    },
+    FlagDef {
+        id: "backend-console",
+        description: "Show the backend's console instead of logging its output (troubleshooting; applies when the backend next starts)",
+        default: false,
+    },
+    FlagDef {
+        id: "automation",
+        description:
+            "Test hooks for end-to-end runs: fixed window titles and a state reset command",
+        default: false,
+    },
+];
+
+const ENV_VAR: &str = "BRAINSHAPE_FLAGS";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FlagInfo {
+    id: &'static str,
+    description: &'static str,
+    enabled: bool,
+    /// Whether the settings or `BRAINSHAPE_FLAGS` differ from the default.
+    overridden: bool,
+}
+
+fn find(id: &str) -> AppResult<&'static FlagDef> {
+    FLAGS
+        .iter()
+        .find(|flag| flag.id == id)
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown feature flag: {id}")))
+}
+
+/// `a,-b` turns `a` on and `b` off. Unknown ids have no effect.
+fn parse_env(value: &str) -> BTreeMap<String, bool> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.strip_prefix('-') {
+            Some(id) => (id.to_string(), false),
+            None => (item.to_string(), true),
+        })
+        .collect()
+}
+
+/// Stored overrides with `BRAINSHAPE_FLAGS` applied on top.
+fn overrides(stored: &settings::DesktopSettings) -> BTreeMap<String, bool> {
+    let mut overrides = stored.feature_flags.clone();
+    if let Ok(value) = std::env::var(ENV_VAR) {
+        overrides.extend(parse_env(&value));
+    }
+    overrides
+}
+
+fn resolve(flag: &'static FlagDef, overrides: &BTreeMap<String, bool>) -> FlagInfo {
+    let stored = overrides.get(flag.id).copied();
+    FlagInfo {
+        id: flag.id,
+        description: flag.description,
+        enabled: stored.unwrap_or(flag.default),
+        overridden: stored.is_some_and(|enabled| enabled != flag.default),
+    }
+}
+
+fn list(stored: &settings::DesktopSettings) -> Vec<FlagInfo> {
+    let overrides = overrides(stored);
+    FLAGS.iter().map(|flag| resolve(flag, &overrides)).collect()
+}
+
+/// Whether `id` is on for this user. Unknown flags are off.
+pub fn is_enabled(app: &AppHandle, id: &str) -> bool {
+    find(id).is_ok_and(|flag| resolve(flag, &overrides(&settings::get(app))).enabled)
+}
+
+#[tauri::command]
+pub fn list_feature_flags(app: AppHandle) -> Vec<FlagInfo> {
+    list(&settings::get(&app))
+}
+
+#[tauri::command]
+pub fn is_feature_enabled(app: AppHandle, id: String) -> AppResult<bool> {
+    Ok(resolve(find(&id)?, &overrides(&settings::get(&app))).enabled)
+}
+
+/// Override a flag, or with `enabled: None` go back to its default.
+/// `BRAINSHAPE_FLAGS` still wins for the current launch.
+#[tauri::command]
+pub fn set_feature_flag(
+    app: AppHandle,
+    id: String,
+    enabled: Option<bool>,
+) -> AppResult<Vec<FlagInfo>> {
+    let flag = find(&id)?;
+    settings::update(&app, |stored| match enabled {
+        Some(enabled) => {
+            stored.feature_flags.insert(flag.id.to_string(), enabled);
+        }
+        None => {
+            stored.feature_flags.remove(flag.id);
+        }
+    })?;
+    eprintln!("[flags] {} set to {:?}", flag.id, enabled);
+    let flags = list(&settings::get(&app));
+    let _ = chaos::emit(&app, "feature-flags-changed", flags.clone());
+    Ok(flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_take_precedence_over_defaults() {
+        let flag = find("drop-import").unwrap();
+        let mut overrides = BTreeMap::new();
+        assert!(resolve(flag, &overrides).enabled);
+        assert!(!resolve(flag, &overrides).overridden);
+
+        overrides.insert(flag.id.into(), false);
+        let info = resolve(flag, &overrides);
+        assert!(!info.enabled);
+        assert!(info.overridden);
+
+        assert!(find("no-such-flag").is_err());
+    }
+
+    #[test]
+    fn parses_env_overrides() {
+        let parsed = parse_env("automation, -drop-import,,");
+        assert_eq!(parsed.get("automation"), Some(&true));
+        assert_eq!(parsed.get("drop-import"), Some(&false));
+        assert_eq!(parsed.len(), 2);
+    }
+}
@@ -1,109 +1,218 @@
-use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
-use std::io::{BufRead, BufReader};
-
 use tauri::Manager;
 
-/// State shared between the Tauri setup and commands.
-struct BackendState {
-    port: u16,
-}
+mod actions;
+mod activity;
+mod archive;
+mod audit;
+mod automation;
+mod backend;
+mod backup;
+mod chaos;
+mod cli;
+mod connections;
+mod deep_link;
+// `error` and `sidecar` are public for the integration tests in `tests/`.
+pub mod error;
+mod external;
+mod feedback;
+mod file_drop;
+mod flags;
+mod mock_backend;
+mod notifications;
+mod paths;
+mod python_env;
+mod recovery;
+mod sandbox;
+mod settings;
+mod shortcuts;
+pub mod sidecar;
+mod signature;
+mod tasks;
+mod throttle;
+mod tunnel;
+mod workspace;
 
-/// Returns the port the Python backend is listening on.
-#[tauri::command]
-fn get_backend_port(state: tauri::State<'_, Mutex<BackendState>>) -> u16 {
-    state.lock().unwrap().port
+/// A second launch exits immediately (before it can spawn another sidecar on
+/// the same port) and hands its arguments to this instance. Deep links in
+/// them are forwarded to the deep-link plugin automatically.
+fn on_second_instance(app: &tauri::AppHandle, args: Vec<String>, cwd: String) {
+    actions::show_window(app);
+    match cli::Cli::parse(args.into_iter().skip(1).map(Into::into)) {
+        Ok(cli) => {
+            let cwd = std::path::Path::new(&cwd);
+            let paths = cli.open.into_iter().map(|path| cwd.join(path)).collect();
+            file_drop::open_paths(app, paths);
+        }
+        Err(e) => eprintln!("[cli] Ignoring arguments from second launch: {}", e),
+    }
 }
 
-/// Default port for the Brainshape backend server.
-/// Fixed so external MCP clients can reliably connect.
-const DEFAULT_PORT: u16 = 52836;
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let cli = match cli::Cli::parse(std::env::args_os().skip(1)) {
+        Ok(cli) if cli.help => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    for flag in &cli.ignored {
+        eprintln!("[cli] Ignoring unknown argument: {}", flag);
+    }
+    sandbox::prepare();
+
     tauri::Builder::default()
+        // Must be registered first so a second instance exits before other plugins start.
+        .plugin(tauri_plugin_single_instance::init(on_second_instance))
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
-            // In debug builds, the developer runs the Python server manually.
-            // Use the default dev port and skip sidecar spawn.
-            if cfg!(debug_assertions) {
-                app.manage(Mutex::new(BackendState { port: DEFAULT_PORT }));
+        .plugin(tauri_plugin_deep_link::init())
+        .setup(move |app| {
+            // Apply a staged restore before anything reads app data or the
+            // backend opens its database.
+            backup::apply_pending_restore(app.handle());
+            chaos::init(app.handle(), cli.chaos);
+            throttle::init(app.handle());
+            settings::init(app.handle());
+            paths::init(app.handle());
+            audit::init(app.handle());
+            notifications::init(app.handle());
+            backup::report_restore_outcome(app.handle());
+            actions::init(app.handle());
+            activity::init(app.handle());
+            file_drop::init(app.handle());
+            deep_link::init(app.handle());
+            file_drop::open_paths(app.handle(), cli.open);
+            recovery::init(app.handle());
+            workspace::init(app.handle());
+            tasks::init(app.handle());
+            shortcuts::init(app.handle());
+            automation::init(app.handle());
+            tunnel::init(app.handle());
+
+            if cli.mock_backend {
+                let port = mock_backend::start(backend::DEFAULT_PORT, chaos::shared(app.handle()))?;
+                eprintln!("[backend] Serving mock backend on port {}", port);
+                backend::use_external(app.handle(), port);
                 return Ok(());
             }
 
-            let port = DEFAULT_PORT;
-
-            // Find the sidecar in the bundled resources directory.
-            let resource_dir = app
-                .path()
-                .resource_dir()
-                .expect("Failed to resolve resource directory");
-            let sidecar_exe = resource_dir
-                .join("resources")
-                .join("brainshape-server")
-                .join("brainshape-server");
-
-            if !sidecar_exe.exists() {
-                eprintln!(
-                    "[backend] Sidecar not found at: {}",
-                    sidecar_exe.display()
-                );
-                app.manage(Mutex::new(BackendState { port }));
+            // With --backend-url the backend is already running.
+            if let Some(port) = cli.backend_port {
+                eprintln!("[backend] Using existing backend on port {}", port);
+                backend::use_external(app.handle(), port);
                 return Ok(());
             }
 
-            // Spawn the sidecar with stdout/stderr piped for forwarding.
-            let mut child = Command::new(&sidecar_exe)
-                .args(["--port", &port.to_string()])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .expect("Failed to spawn backend sidecar");
-
-            // Forward stdout in a background thread.
-            if let Some(stdout) = child.stdout.take() {
-                std::thread::spawn(move || {
-                    let reader = BufReader::new(stdout);
-                    for line in reader.lines() {
-                        if let Ok(line) = line {
-                            println!("[backend] {}", line);
-                        }
-                    }
-                });
+            // A saved default connection replaces the built-in backend.
+            if connections::use_default(app.handle(), cli.verbose, cli.allow_unsigned_backend) {
+                return Ok(());
             }
 
-            // Forward stderr in a background thread.
-            if let Some(stderr) = child.stderr.take() {
-                std::thread::spawn(move || {
-                    let reader = BufReader::new(stderr);
-                    for line in reader.lines() {
-                        if let Ok(line) = line {
-                            eprintln!("[backend] {}", line);
-                        }
-                    }
-                });
+            // In debug builds, the developer runs the Python server manually
+            // (or has the app run their dev command). Skip sidecar spawn.
+            if cfg!(debug_assertions) {
+                backend::use_dev(app.handle());
+                return Ok(());
             }
 
-            // Keep a handle so we can kill the child on shutdown.
-            app.manage(Mutex::new(Some(child)));
-            app.manage(Mutex::new(BackendState { port }));
-
+            backend::start(app.handle(), cli.verbose, cli.allow_unsigned_backend);
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::Destroyed = event {
-                // Kill the sidecar when the last window closes.
-                if let Some(child_state) = window.try_state::<Mutex<Option<Child>>>() {
-                    if let Ok(mut guard) = child_state.lock() {
-                        if let Some(ref mut child) = *guard {
-                            let _ = child.kill();
-                        }
-                    }
-                }
+        .on_window_event(|window, event| match event {
+            // Kill the sidecar when the main window closes.
+            tauri::WindowEvent::Destroyed if window.label() == "main" => {
+                backend::stop(window.app_handle());
+            }
+            tauri::WindowEvent::CloseRequested { .. }
+                if window.label() == backend::ERROR_WINDOW =>
+            {
+                window.app_handle().exit(0);
             }
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                file_drop::handle_drop(window.app_handle(), paths.clone());
+            }
+            _ => {}
         })
-        .invoke_handler(tauri::generate_handler![get_backend_port])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            actions::list_actions,
+            actions::invoke_action,
+            actions::set_action_enabled,
+            activity::begin_activity,
+            activity::end_activity,
+            archive::import_archive,
+            audit::export_audit_log,
+            automation::automation_reset_state,
+            backend::get_backend_port,
+            backend::get_startup_error,
+            backend::retry_backend,
+            backend::run_without_backend,
+            backend::open_backend_logs,
+            backup::create_backup,
+            backup::restore_backup,
+            chaos::get_chaos,
+            chaos::set_chaos,
+            connections::list_connections,
+            connections::save_connection,
+            connections::remove_connection,
+            connections::test_connection,
+            connections::set_default_connection,
+            connections::switch_connection,
+            chaos::chaos_crash_backend,
+            deep_link::take_pending_deep_links,
+            external::open_note_externally,
+            external::reveal_note,
+            feedback::report_problem,
+            file_drop::ready_for_opened_files,
+            flags::list_feature_flags,
+            flags::is_feature_enabled,
+            flags::set_feature_flag,
+            notifications::list_notifications,
+            notifications::push_notification,
+            notifications::mark_notifications_read,
+            notifications::clear_notifications,
+            notifications::unread_notification_count,
+            paths::pick_file,
+            paths::pick_save_file,
+            recovery::save_recovery_snapshot,
+            recovery::get_recovery_snapshots,
+            recovery::restore_snapshot,
+            recovery::discard_recovery_snapshot,
+            sandbox::get_sandbox,
+            shortcuts::get_shortcuts,
+            shortcuts::set_shortcut,
+            tasks::list_tasks,
+            tasks::cancel_task,
+            workspace::get_workspace_usage,
+            workspace::release_workspace_dir,
+            tunnel::get_tunnel_status,
+            python_env::get_python_interpreter,
+            python_env::choose_python_env,
+            python_env::clear_python_env,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            match event {
+                tauri::RunEvent::Exit => {
+                    // Also covers a sidecar that finished spawning after
+                    // the main window was gone.
+                    backend::stop(app);
+                    tunnel::close(app);
+                    recovery::shutdown(app);
+                    workspace::shutdown(app);
+                }
+                // macOS delivers "open with" as an Apple event rather than argv.
+                #[cfg(target_os = "macos")]
+                tauri::RunEvent::Opened { urls } => {
+                    let paths = urls.iter().filter_map(|u| u.to_file_path().ok()).collect();
+                    file_drop::open_paths(app, paths);
+                }
+                _ => {}
+            }
+        });
 }
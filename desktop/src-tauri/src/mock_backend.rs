@@ -0,0 +1,563 @@
+//! `--mock-backend`: a canned stand-in for the Python server.
+//!
+//! Serves fixture responses for the endpoints the UI needs to start up and
+//! be clicked through (notes, search, graph, memories, settings, sync) plus a
+//! slowly streamed agent reply, so the frontend can be developed or demoed
+//! without a Python environment. Note edits are kept in memory only. It is a
+//! plain HTTP/1.1 server on a thread; every response closes the connection.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::chaos::SharedChaos;
+use crate::error::LockExt;
+
+/// Same allowlist as the backend's CORS middleware in `server.py`.
+const ALLOWED_ORIGINS: &[&str] = &[
+    "http://localhost:1420",
+    "http://localhost:5173",
+    "tauri://localhost",
+    "https://tauri.localhost",
+];
+
+const FIXTURE_NOTES: &[(&str, &str)] = &[
+    (
+        "Welcome.md",
+        "---\ntags: [demo]\n---\n# Welcome\n\nThis is the **mock backend**. Nothing here is \
+         saved to disk.\n\nTry opening [[Roadmap]] or [[Meeting Notes]].\n",
+    ),
+    (
+        "Projects/Roadmap.md",
+        "---\ntags: [demo, planning]\n---\n# Roadmap\n\n- Ship the graph view\n- Polish \
+         search\n\nDiscussed in [[Meeting Notes]].\n",
+    ),
+    (
+        "Meeting Notes.md",
+        "---\ntags: [meetings]\n---\n# Meeting Notes\n\nAgreed to prioritise the \
+         [[Roadmap]].\n",
+    ),
+];
+
+const AGENT_REPLY: &[&str] = &[
+    "This ",
+    "is ",
+    "a ",
+    "canned ",
+    "reply ",
+    "from ",
+    "the ",
+    "mock ",
+    "backend. ",
+    "Your ",
+    "notes ",
+    "mention ",
+    "the ",
+    "[[Roadmap]] ",
+    "twice.",
+];
+
+/// Delay between streamed agent tokens, so streaming UI is visible.
+const TOKEN_DELAY: Duration = Duration::from_millis(60);
+
+/// Largest request body read into memory; bigger ones get a 413 unread.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+struct MockState {
+    notes: BTreeMap<String, String>,
+    settings: Value,
+}
+
+impl MockState {
+    fn new() -> Self {
+        Self {
+            notes: FIXTURE_NOTES
+                .iter()
+                .map(|(path, content)| (path.to_string(), content.to_string()))
+                .collect(),
+            settings: json!({
+                "notes_path": "(mock)",
+                "llm_provider": "mock",
+                "llm_model": "mock",
+                "ollama_base_url": "http://localhost:11434",
+                "anthropic_api_key_set": false,
+                "openai_api_key_set": false,
+                "mistral_api_key_set": false,
+                "transcription_provider": "local",
+                "transcription_model": "",
+                "mcp_servers": [],
+                "theme": {},
+                "custom_themes": [],
+                "font_family": "",
+                "editor_font_size": 14,
+                "editor_keymap": "default",
+                "editor_line_numbers": false,
+                "editor_word_wrap": true,
+                "editor_inline_formatting": true,
+            }),
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    origin: Option<String>,
+    body: Value,
+    /// The body was over `MAX_BODY` and wasn't read.
+    too_large: bool,
+}
+
+enum Response {
+    Json(u16, Value),
+    /// Server-sent events, written one at a time with `TOKEN_DELAY` between.
+    Events(Vec<Value>),
+    NoContent,
+}
+
+fn not_found(detail: &str) -> Response {
+    Response::Json(404, json!({ "detail": detail }))
+}
+
+/// Bind `port` on loopback, or any free port if it's taken (e.g. by a real
+/// backend), and serve until the app exits. Returns the bound port. With
+/// `chaos`, responses are delayed by its `response_delay_ms`.
+pub fn start(port: u16, chaos: Option<SharedChaos>) -> io::Result<u16> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .or_else(|_| TcpListener::bind((Ipv4Addr::LOCALHOST, 0)))?;
+    let port = listener.local_addr()?.port();
+    let state = Arc::new(Mutex::new(MockState::new()));
+    std::thread::spawn(move || {
+        for stream in listener.incoming().map_while(Result::ok) {
+            let state = state.clone();
+            let chaos = chaos.clone();
+            std::thread::spawn(move || {
+                if let Some(chaos) = chaos {
+                    let delay = chaos.lock_or_recover().response_delay_ms;
+                    std::thread::sleep(Duration::from_millis(delay));
+                }
+                if let Err(e) = handle(stream, &state) {
+                    eprintln!("[mock-backend] {}", e);
+                }
+            });
+        }
+    });
+    Ok(port)
+}
+
+fn handle(stream: TcpStream, state: &Mutex<MockState>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = read_request(&mut reader)?;
+    let response = if request.too_large {
+        Response::Json(413, json!({ "detail": "Request body too large" }))
+    } else {
+        route(&mut state.lock_or_recover(), &request)
+    };
+    write_response(stream, request.origin.as_deref(), response)
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts
+        .next()
+        .ok_or_else(|| invalid("empty request"))?
+        .to_string();
+    let target = parts.next().ok_or_else(|| invalid("missing path"))?;
+    let path = percent_decode(target.split('?').next().unwrap_or_default());
+
+    let mut content_length = 0;
+    let mut origin = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.parse().unwrap_or(0),
+                "origin" => origin = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+    let too_large = content_length > MAX_BODY;
+    let mut body = Value::Null;
+    if !too_large {
+        let mut bytes = vec![0; content_length];
+        reader.read_exact(&mut bytes)?;
+        body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    }
+    Ok(Request {
+        method,
+        path,
+        origin,
+        body,
+        too_large,
+    })
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn title_of(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Targets of `[[wikilinks]]` in `content`.
+fn links_in(content: &str) -> Vec<String> {
+    content
+        .split("[[")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("]]").map(|(link, _)| link.to_string()))
+        .collect()
+}
+
+/// Tags from a `tags: [a, b]` frontmatter line.
+fn tags_in(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("tags: ["))
+        .and_then(|rest| rest.strip_suffix(']'))
+        .map(|list| list.split(',').map(|t| t.trim().to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn note_json(path: &str, content: &str) -> Value {
+    json!({
+        "path": path,
+        "title": title_of(path),
+        "content": content,
+        "metadata": {},
+        "links": links_in(content),
+        "tags": tags_in(content),
+    })
+}
+
+fn search(state: &MockState, body: &Value) -> Response {
+    let query = body["query"].as_str().unwrap_or_default().to_lowercase();
+    let tag = body["tag"].as_str();
+    let results: Vec<Value> = state
+        .notes
+        .iter()
+        .filter(|(_, content)| content.to_lowercase().contains(&query))
+        .filter(|(_, content)| tag.is_none_or(|tag| tags_in(content).iter().any(|t| t == tag)))
+        .map(|(path, content)| {
+            json!({
+                "title": title_of(path),
+                "path": path,
+                "snippet": content.chars().take(300).collect::<String>(),
+                "score": 1.0,
+            })
+        })
+        .collect();
+    Response::Json(200, json!({ "results": results }))
+}
+
+fn graph(state: &MockState) -> Value {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let mut tags = BTreeSet::new();
+    for (path, content) in &state.notes {
+        let id = format!("note:{path}");
+        nodes.push(json!({ "id": id, "label": "Note", "name": title_of(path), "path": path, "type": null }));
+        for link in links_in(content) {
+            if let Some(target) = state.notes.keys().find(|p| title_of(p) == link) {
+                edges.push(
+                    json!({ "source": id, "target": format!("note:{target}"), "type": "LINKS_TO" }),
+                );
+            }
+        }
+        for tag in tags_in(content) {
+            edges.push(
+                json!({ "source": id, "target": format!("tag:{tag}"), "type": "TAGGED_WITH" }),
+            );
+            tags.insert(tag);
+        }
+    }
+    for tag in tags {
+        nodes.push(json!({ "id": format!("tag:{tag}"), "label": "Tag", "name": tag, "path": null, "type": null }));
+    }
+    json!({ "nodes": nodes, "edges": edges })
+}
+
+/// Counts per node label and relationship type, as `/graph/stats` reports.
+fn graph_stats(state: &MockState) -> Value {
+    let graph = graph(state);
+    let count_by = |key: &str, field: &str| {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for item in graph[key].as_array().into_iter().flatten() {
+            *counts
+                .entry(item[field].as_str().unwrap_or_default().to_string())
+                .or_default() += 1;
+        }
+        counts
+    };
+    let mut nodes = count_by("nodes", "label");
+    nodes.insert("Memory".into(), 2);
+    json!({ "nodes": nodes, "relationships": count_by("edges", "type") })
+}
+
+fn note_route(state: &mut MockState, req: &Request, path: &str) -> Response {
+    match req.method.as_str() {
+        "GET" => match state.notes.get(path) {
+            Some(content) => Response::Json(200, note_json(path, content)),
+            None => not_found("Note not found"),
+        },
+        "PUT" => match state.notes.get_mut(path) {
+            Some(content) => {
+                *content = req.body["content"].as_str().unwrap_or_default().to_string();
+                Response::Json(200, json!({ "path": path, "title": title_of(path) }))
+            }
+            None => not_found("Note not found"),
+        },
+        "DELETE" => match state.notes.remove(path) {
+            Some(_) => Response::Json(200, json!({ "status": "ok" })),
+            None => not_found("Note not found"),
+        },
+        _ => not_found("Not available in mock mode"),
+    }
+}
+
+fn route(state: &mut MockState, req: &Request) -> Response {
+    if req.method == "OPTIONS" {
+        return Response::NoContent;
+    }
+    if let Some(path) = req.path.strip_prefix("/notes/file/") {
+        return note_route(state, req, path);
+    }
+    match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/health") => Response::Json(
+            200,
+            json!({ "status": "ok", "ready": true, "surrealdb_connected": true, "agent_available": true }),
+        ),
+        ("GET", "/config") => Response::Json(
+            200,
+            json!({ "notes_path": "(mock)", "model_name": "mock", "surrealdb_path": "(mock)" }),
+        ),
+        ("GET", "/settings") => Response::Json(200, state.settings.clone()),
+        ("PUT", "/settings") => {
+            if let (Some(settings), Some(updates)) =
+                (state.settings.as_object_mut(), req.body.as_object())
+            {
+                for (key, value) in updates {
+                    if !key.ends_with("api_key") {
+                        settings.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            Response::Json(200, state.settings.clone())
+        }
+        ("GET", "/notes/files") => {
+            let files: Vec<Value> = state
+                .notes
+                .keys()
+                .map(|path| json!({ "path": path, "title": title_of(path) }))
+                .collect();
+            let folders: BTreeSet<String> = state
+                .notes
+                .keys()
+                .filter_map(|path| Path::new(path).parent())
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map(|parent| parent.to_string_lossy().into_owned())
+                .collect();
+            Response::Json(200, json!({ "files": files, "folders": folders }))
+        }
+        ("POST", "/notes/file") => {
+            let title = req.body["title"].as_str().unwrap_or("Untitled");
+            let path = match req.body["folder"].as_str().filter(|f| !f.is_empty()) {
+                Some(folder) => format!("{folder}/{title}.md"),
+                None => format!("{title}.md"),
+            };
+            let content = req.body["content"].as_str().unwrap_or_default();
+            state.notes.insert(path.clone(), content.to_string());
+            Response::Json(200, json!({ "path": path, "title": title }))
+        }
+        ("GET", "/notes/tags") => {
+            let tags: BTreeSet<String> = state.notes.values().flat_map(|c| tags_in(c)).collect();
+            Response::Json(200, json!({ "tags": tags }))
+        }
+        ("GET", "/notes/trash") => Response::Json(200, json!({ "files": [] })),
+        ("POST", "/search/keyword" | "/search/semantic") => search(state, &req.body),
+        ("GET", "/graph/overview") => Response::Json(200, graph(state)),
+        ("GET", "/graph/stats") => Response::Json(200, graph_stats(state)),
+        ("GET", "/graph/memories") => Response::Json(
+            200,
+            json!({ "memories": [
+                { "id": "mock-1", "type": "preference", "content": "Prefers short meeting notes", "created_at": null, "connections": [] },
+                { "id": "mock-2", "type": "fact", "content": "Working on the Roadmap project", "created_at": null,
+                  "connections": [{ "name": "Roadmap", "relationship": "ABOUT" }] },
+            ]}),
+        ),
+        ("POST", "/sync/structural" | "/sync/semantic" | "/sync/full") => Response::Json(
+            200,
+            json!({ "status": "ok", "stats": { "notes": state.notes.len(), "tags": 0, "links": 0, "pruned": 0 } }),
+        ),
+        ("POST", "/agent/init") => Response::Json(200, json!({ "session_id": "mock-session" })),
+        ("POST", "/agent/message") => {
+            let mut events =
+                vec![json!({ "name": "search_notes", "args": { "query": req.body["message"] } })];
+            events.extend(AGENT_REPLY.iter().map(|token| json!(token)));
+            Response::Events(events)
+        }
+        ("GET", "/ollama/models") => Response::Json(200, json!({ "models": [] })),
+        _ => not_found("Not available in mock mode"),
+    }
+}
+
+fn write_response(
+    mut stream: TcpStream,
+    origin: Option<&str>,
+    response: Response,
+) -> io::Result<()> {
+    let mut head = String::new();
+    if let Some(origin) = origin.filter(|o| ALLOWED_ORIGINS.contains(o)) {
+        head.push_str(&format!(
+            "Access-Control-Allow-Origin: {origin}\r\n\
+             Access-Control-Allow-Methods: GET, POST, PUT, DELETE, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n"
+        ));
+    }
+    match response {
+        Response::NoContent => write!(
+            stream,
+            "HTTP/1.1 204 No Content\r\n{head}Connection: close\r\n\r\n"
+        ),
+        Response::Json(status, body) => {
+            let body = body.to_string();
+            let reason = match status {
+                200 => "OK",
+                413 => "Payload Too Large",
+                _ => "Not Found",
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {status} {reason}\r\n{head}Content-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+        }
+        Response::Events(events) => {
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\n{head}Content-Type: text/event-stream\r\n\
+                 Cache-Control: no-cache\r\nConnection: close\r\n\r\n"
+            )?;
+            for event in events {
+                write!(stream, "data: {event}\r\n\r\n")?;
+                stream.flush()?;
+                std::thread::sleep(TOKEN_DELAY);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn request(method: &str, path: &str, body: Value) -> Request {
+        Request {
+            method: method.into(),
+            path: path.into(),
+            origin: None,
+            body,
+            too_large: false,
+        }
+    }
+
+    fn json_of(response: Response) -> (u16, Value) {
+        match response {
+            Response::Json(status, body) => (status, body),
+            _ => panic!("expected a JSON response"),
+        }
+    }
+
+    #[test]
+    fn edits_notes_in_memory() {
+        let mut state = MockState::new();
+        let (status, note) = json_of(route(
+            &mut state,
+            &request("GET", "/notes/file/Projects/Roadmap.md", Value::Null),
+        ));
+        assert_eq!(status, 200);
+        assert_eq!(note["links"], json!(["Meeting Notes"]));
+        assert_eq!(note["tags"], json!(["demo", "planning"]));
+
+        let update = json!({ "content": "rewritten" });
+        json_of(route(
+            &mut state,
+            &request("PUT", "/notes/file/Welcome.md", update),
+        ));
+        assert_eq!(state.notes["Welcome.md"], "rewritten");
+        let (status, _) = json_of(route(
+            &mut state,
+            &request("GET", "/notes/file/Missing.md", Value::Null),
+        ));
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn serves_over_http_with_cors() {
+        let port = start(0, None).unwrap();
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        write!(
+            stream,
+            "GET /notes/file/Meeting%20Notes.md HTTP/1.1\r\nOrigin: tauri://localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("Access-Control-Allow-Origin: tauri://localhost"));
+        assert!(response.contains("\"title\":\"Meeting Notes\""));
+    }
+
+    #[test]
+    fn refuses_oversized_bodies() {
+        let port = start(0, None).unwrap();
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        write!(
+            stream,
+            "PUT /settings HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"));
+    }
+}
@@ -0,0 +1,275 @@
+//! In-app notification center.
+//!
+//! Notifications (backend warnings, import results, errors surfaced by the
+//! frontend) are kept in one store owned by the Rust shell and persisted to
+//! `notifications.json` in the app data directory. Every change emits a
+//! `notifications-changed` event carrying the unread count for the badge.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::LockExt;
+use crate::throttle;
+
+/// Oldest notifications are dropped beyond this many.
+const MAX_NOTIFICATIONS: usize = 200;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: u64,
+    pub level: Level,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Unix timestamp in seconds.
+    pub created_at: u64,
+    #[serde(default)]
+    pub read: bool,
+}
+
+/// Payload of the `notifications-changed` event.
+#[derive(Clone, Serialize)]
+struct NotificationsChanged {
+    unread: usize,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct NotificationStore {
+    next_id: u64,
+    items: Vec<Notification>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl NotificationStore {
+    /// Load the store from `path`, starting empty if it is missing or corrupt.
+    pub fn load(path: PathBuf) -> Self {
+        let mut store = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                eprintln!("[notifications] Ignoring corrupt {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        store.path = Some(path);
+        store
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string(self) {
+            Ok(text) => {
+                if let Err(e) = fs::write(path, text) {
+                    eprintln!("[notifications] Failed to write {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => eprintln!("[notifications] Failed to serialize: {}", e),
+        }
+    }
+
+    pub fn push(&mut self, level: Level, title: String, body: Option<String>) -> Notification {
+        self.next_id += 1;
+        let notification = Notification {
+            id: self.next_id,
+            level,
+            title,
+            body,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            read: false,
+        };
+        self.items.push(notification.clone());
+        if self.items.len() > MAX_NOTIFICATIONS {
+            let excess = self.items.len() - MAX_NOTIFICATIONS;
+            self.items.drain(..excess);
+        }
+        self.save();
+        notification
+    }
+
+    /// Mark the given notifications as read, or all of them when `ids` is `None`.
+    pub fn mark_read(&mut self, ids: Option<&[u64]>) {
+        for item in &mut self.items {
+            if ids.is_none_or(|ids| ids.contains(&item.id)) {
+                item.read = true;
+            }
+        }
+        self.save();
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.save();
+    }
+
+    pub fn unread(&self) -> usize {
+        self.items.iter().filter(|n| !n.read).count()
+    }
+
+    /// Newest first, as the notification list displays them.
+    pub fn list(&self) -> Vec<Notification> {
+        self.items.iter().rev().cloned().collect()
+    }
+}
+
+/// Load the persisted store into managed state.
+pub fn init(app: &AppHandle) {
+    let path = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("notifications.json"))
+        .ok();
+    let store = match path {
+        Some(path) => NotificationStore::load(path),
+        None => NotificationStore::default(),
+    };
+    app.manage(Mutex::new(store));
+}
+
+fn emit_changed(app: &AppHandle, store: &NotificationStore) {
+    throttle::emit_latest(
+        app,
+        "notifications-changed",
+        NotificationsChanged {
+            unread: store.unread(),
+        },
+    );
+}
+
+/// Add a notification from Rust code (e.g. backend startup warnings).
+pub fn notify(app: &AppHandle, level: Level, title: impl Into<String>, body: Option<String>) {
+    let Some(state) = app.try_state::<Mutex<NotificationStore>>() else {
+        return;
+    };
+    let mut store = state.lock_or_recover();
+    store.push(level, title.into(), body);
+    emit_changed(app, &store);
+}
+
+/// Remove every notification.
+pub fn clear(app: &AppHandle) {
+    let Some(state) = app.try_state::<Mutex<NotificationStore>>() else {
+        return;
+    };
+    let mut store = state.lock_or_recover();
+    store.clear();
+    emit_changed(app, &store);
+}
+
+#[tauri::command]
+pub fn list_notifications(state: State<'_, Mutex<NotificationStore>>) -> Vec<Notification> {
+    state.lock_or_recover().list()
+}
+
+#[tauri::command]
+pub fn push_notification(
+    app: AppHandle,
+    state: State<'_, Mutex<NotificationStore>>,
+    level: Level,
+    title: String,
+    body: Option<String>,
+) -> Notification {
+    let mut store = state.lock_or_recover();
+    let notification = store.push(level, title, body);
+    emit_changed(&app, &store);
+    notification
+}
+
+/// Mark notifications as read; omit `ids` to mark everything read.
+#[tauri::command]
+pub fn mark_notifications_read(
+    app: AppHandle,
+    state: State<'_, Mutex<NotificationStore>>,
+    ids: Option<Vec<u64>>,
+) {
+    let mut store = state.lock_or_recover();
+    store.mark_read(ids.as_deref());
+    emit_changed(&app, &store);
+}
+
+#[tauri::command]
+pub fn clear_notifications(app: AppHandle) {
+    clear(&app);
+}
+
+#[tauri::command]
+pub fn unread_notification_count(state: State<'_, Mutex<NotificationStore>>) -> usize {
+    state.lock_or_recover().unread()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_mark_read() {
+        let mut store = NotificationStore::default();
+        let first = store.push(Level::Info, "one".into(), None);
+        store.push(Level::Error, "two".into(), Some("details".into()));
+        assert_eq!(store.unread(), 2);
+
+        store.mark_read(Some(&[first.id]));
+        assert_eq!(store.unread(), 1);
+        assert_eq!(store.list()[0].title, "two");
+
+        store.mark_read(None);
+        assert_eq!(store.unread(), 0);
+    }
+
+    #[test]
+    fn drops_oldest_beyond_cap() {
+        let mut store = NotificationStore::default();
+        for i in 0..MAX_NOTIFICATIONS + 5 {
+            store.push(Level::Info, format!("n{i}"), None);
+        }
+        let list = store.list();
+        assert_eq!(list.len(), MAX_NOTIFICATIONS);
+        assert_eq!(list.last().unwrap().title, "n5");
+    }
+
+    #[test]
+    fn persists_across_loads() {
+        let path = std::env::temp_dir().join(format!(
+            "brainshape-notifications-{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let mut store = NotificationStore::load(path.clone());
+        store.push(Level::Warning, "saved".into(), None);
+
+        let mut reloaded = NotificationStore::load(path.clone());
+        assert_eq!(reloaded.list().len(), 1);
+        assert_eq!(reloaded.push(Level::Info, "next".into(), None).id, 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn corrupt_file_starts_empty() {
+        let path = std::env::temp_dir().join(format!(
+            "brainshape-notifications-corrupt-{}.json",
+            std::process::id()
+        ));
+        fs::write(&path, "{not json").unwrap();
+        assert_eq!(NotificationStore::load(path.clone()).list().len(), 0);
+        fs::remove_file(&path).unwrap();
+    }
+}
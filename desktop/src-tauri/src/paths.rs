@@ -0,0 +1,266 @@
+//! Path authorization for every command that takes a filesystem path.
+//!
+//! The webview is treated as untrusted: a path from it is only used if it
+//! resolves (after canonicalizing, so symlinks and `..` can't escape) inside
+//! a scope:
+//!
+//! - the notes folder, for notes-relative paths ([`resolve_note`]);
+//! - the session workspace, for staging directories (`Workspace::release`);
+//! - a file or folder the user granted by picking it in a dialog opened by
+//!   the shell ([`pick_file`], [`pick_save_file`]), dropping it on the
+//!   window, or opening it with the app.
+//!
+//! Grants last for the session.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::backup::backend_config_dir;
+use crate::error::{AppError, AppResult, LockExt};
+
+/// Matches the backend's fallback in `config.py`.
+const DEFAULT_NOTES_DIR: &str = "~/brainshape";
+
+fn expand_home(app: &AppHandle, path: &str) -> AppResult<PathBuf> {
+    match path.strip_prefix("~") {
+        Some(rest) => {
+            let home = app.path().home_dir()?;
+            Ok(home.join(rest.trim_start_matches(['/', '\\'])))
+        }
+        None => Ok(PathBuf::from(path)),
+    }
+}
+
+/// The notes folder as the backend resolves it: `settings.json`, then
+/// `NOTES_PATH`, then the default.
+pub fn notes_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    let configured = backend_config_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("settings.json")).ok())
+        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+        .and_then(|settings| settings.get("notes_path")?.as_str().map(String::from))
+        .filter(|path| !path.is_empty())
+        .or_else(|| std::env::var("NOTES_PATH").ok())
+        .unwrap_or_else(|| DEFAULT_NOTES_DIR.to_string());
+    expand_home(app, &configured)
+}
+
+#[derive(Default)]
+struct Grants(Mutex<Vec<PathBuf>>);
+
+pub fn init(app: &AppHandle) {
+    app.manage(Grants::default());
+}
+
+/// Whether `path` is a non-empty path made only of plain names (no root,
+/// drive prefix, `.` or `..`).
+pub fn is_plain_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Resolve `relative` inside `root`, following symlinks, and reject anything
+/// that is absolute, uses `..`, is missing, or ends up outside `root`.
+fn resolve_inside(root: &Path, relative: &str) -> AppResult<PathBuf> {
+    if !is_plain_relative(relative) {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid note path: {relative}"
+        )));
+    }
+    let root = root.canonicalize()?;
+    let path = root
+        .join(relative)
+        .canonicalize()
+        .map_err(|e| AppError::NotFound(format!("{relative}: {e}")))?;
+    if !path.starts_with(&root) {
+        return Err(AppError::PermissionDenied(format!(
+            "{relative} is outside the notes folder"
+        )));
+    }
+    Ok(path)
+}
+
+/// A notes-relative path, as the backend API uses them.
+pub fn resolve_note(app: &AppHandle, relative: &str) -> AppResult<PathBuf> {
+    resolve_inside(&notes_dir(app)?, relative)
+}
+
+/// Canonical form of an absolute `path`. With `create`, the file itself may
+/// not exist yet, but its folder must.
+fn canonical(path: &Path, create: bool) -> AppResult<PathBuf> {
+    if !path.is_absolute() || path.components().any(|c| c == Component::ParentDir) {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid path: {}",
+            path.display()
+        )));
+    }
+    if !create || path.exists() {
+        return path
+            .canonicalize()
+            .map_err(|e| AppError::NotFound(format!("{}: {e}", path.display())));
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => Ok(parent
+            .canonicalize()
+            .map_err(|e| AppError::NotFound(format!("{}: {e}", parent.display())))?
+            .join(name)),
+        _ => Err(AppError::InvalidInput(format!(
+            "Invalid path: {}",
+            path.display()
+        ))),
+    }
+}
+
+fn covered(grants: &[PathBuf], path: &Path) -> bool {
+    grants.iter().any(|granted| path.starts_with(granted))
+}
+
+/// Allow later commands to use `path` (and, for a folder, its contents).
+pub fn grant(app: &AppHandle, path: &Path) {
+    let Some(grants) = app.try_state::<Grants>() else {
+        return;
+    };
+    match canonical(path, true) {
+        Ok(path) => grants.0.lock_or_recover().push(path),
+        Err(e) => eprintln!("[paths] Not granting {}: {}", path.display(), e),
+    }
+}
+
+/// Check an absolute path from the frontend against the granted scopes.
+/// With `create`, `path` may name a file that doesn't exist yet.
+pub fn authorize(app: &AppHandle, path: &str, create: bool) -> AppResult<PathBuf> {
+    let resolved = canonical(Path::new(path), create)?;
+    let grants = app.state::<Grants>();
+    if covered(&grants.0.lock_or_recover(), &resolved) {
+        Ok(resolved)
+    } else {
+        Err(AppError::PermissionDenied(format!(
+            "{path} wasn't chosen in a file dialog, dropped, or opened with Brainshape"
+        )))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileFilter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+pub(crate) fn dialog(
+    app: &AppHandle,
+    title: String,
+    filters: &[FileFilter],
+) -> tauri_plugin_dialog::FileDialogBuilder<tauri::Wry> {
+    let mut dialog = app.dialog().file().set_title(title);
+    if let Some(window) = app.get_webview_window("main") {
+        dialog = dialog.set_parent(&window);
+    }
+    for filter in filters {
+        let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+        dialog = dialog.add_filter(&filter.name, &extensions);
+    }
+    dialog
+}
+
+fn picked(
+    app: &AppHandle,
+    path: Option<tauri_plugin_dialog::FilePath>,
+) -> AppResult<Option<String>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let path = path
+        .into_path()
+        .map_err(|e| AppError::Internal(format!("Unusable path from the dialog: {e}")))?;
+    grant(app, &path);
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+/// Ask the user for an existing file and grant access to it. `None` if
+/// cancelled.
+#[tauri::command]
+pub async fn pick_file(
+    app: AppHandle,
+    title: String,
+    filters: Vec<FileFilter>,
+) -> AppResult<Option<String>> {
+    let dialog = dialog(&app, title, &filters);
+    let path = tauri::async_runtime::spawn_blocking(move || dialog.blocking_pick_file()).await?;
+    picked(&app, path)
+}
+
+/// Ask the user where to save a file and grant access to that path. `None`
+/// if cancelled.
+#[tauri::command]
+pub async fn pick_save_file(
+    app: AppHandle,
+    title: String,
+    default_name: Option<String>,
+    filters: Vec<FileFilter>,
+) -> AppResult<Option<String>> {
+    let mut dialog = dialog(&app, title, &filters);
+    if let Some(name) = default_name {
+        dialog = dialog.set_file_name(name);
+    }
+    let path = tauri::async_runtime::spawn_blocking(move || dialog.blocking_save_file()).await?;
+    picked(&app, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("brainshape-paths-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("Projects")).unwrap();
+        root
+    }
+
+    #[test]
+    fn resolves_only_inside_root() {
+        let root = temp_root("notes");
+        fs::write(root.join("Projects/Plan.md"), "# Plan").unwrap();
+
+        let resolved = resolve_inside(&root, "Projects/Plan.md").unwrap();
+        assert!(resolved.ends_with("Projects/Plan.md"));
+        assert!(resolve_inside(&root, "Projects").is_ok());
+        assert!(resolve_inside(&root, "../Plan.md").is_err());
+        assert!(resolve_inside(&root, "/etc/hosts").is_err());
+        assert!(resolve_inside(&root, "").is_err());
+        assert!(resolve_inside(&root, "Missing.md").is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(std::env::temp_dir(), root.join("escape")).unwrap();
+            assert!(resolve_inside(&root, "escape").is_err());
+        }
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn grants_cover_files_and_folder_contents() {
+        let root = temp_root("grants");
+        fs::write(root.join("Projects/vault.zip"), "zip").unwrap();
+        let grants = vec![canonical(&root.join("Projects"), true).unwrap()];
+
+        let inside = canonical(&root.join("Projects/vault.zip"), false).unwrap();
+        assert!(covered(&grants, &inside));
+        let new_file = canonical(&root.join("Projects/backup.zip"), true).unwrap();
+        assert!(covered(&grants, &new_file));
+        assert!(!covered(&grants, &canonical(&root, false).unwrap()));
+
+        assert!(canonical(&root.join("Projects/../Projects/vault.zip"), false).is_err());
+        assert!(canonical(Path::new("Projects/vault.zip"), false).is_err());
+        assert!(canonical(&root.join("Missing/backup.zip"), true).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+//! Running the built-in backend from an existing Python environment.
+//!
+//! An expert option for backend developers: instead of the bundled sidecar,
+//! the shell runs `python -m brainshape.server` with an interpreter from a
+//! virtualenv or conda env that has Brainshape installed (e.g. the repo's
+//! `.venv` after `uv sync`), so backend changes take effect on restart
+//! without rebuilding the PyInstaller bundle. The interpreter is run
+//! directly, without activating its environment.
+//!
+//! The interpreter is run on every launch, so the webview never supplies
+//! its path: the user picks the environment's folder in a dialog the shell
+//! opens, and the shell finds the interpreter inside it. Picking the folder
+//! rather than the `python` file also avoids dialogs that resolve the venv's
+//! symlink to the base interpreter, which would lose the venv.
+//!
+//! Before every launch the interpreter is probed: it must run, have the
+//! `brainshape` distribution installed, and have all of its requirements
+//! installed. Versions aren't compared; `uv sync` takes care of those.
+//! There is no signature check, since the code is the developer's own.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::backend::{self, Connection};
+use crate::error::{AppError, AppResult};
+use crate::paths;
+use crate::settings;
+use crate::sidecar;
+
+/// Prints the Python version, the installed `brainshape` version, and which
+/// of its requirements (outside extras) are missing, as JSON.
+const PROBE: &str = r#"
+import json, re, sys
+from importlib import metadata
+try:
+    version = metadata.version("brainshape")
+    missing = []
+    for req in metadata.requires("brainshape") or []:
+        if "extra ==" in req:
+            continue
+        name = re.match(r"[A-Za-z0-9._-]+", req).group(0)
+        try:
+            metadata.version(name)
+        except metadata.PackageNotFoundError:
+            missing.append(name)
+except metadata.PackageNotFoundError:
+    version, missing = None, ["brainshape"]
+print(json.dumps({"python": sys.version.split()[0], "brainshape": version, "missing": missing}))
+"#;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PythonEnv {
+    /// e.g. `3.13.1`.
+    python: String,
+    /// The installed Brainshape version, if any.
+    brainshape: Option<String>,
+    /// Distributions Brainshape needs that aren't installed.
+    missing: Vec<String>,
+}
+
+impl PythonEnv {
+    /// Fail unless the backend can run in this environment.
+    fn check(&self, interpreter: &Path) -> AppResult<()> {
+        if self.missing.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::Unavailable(format!(
+            "{} is missing {}. Run `uv sync` in the repository, or `pip install -e .` \
+             with that interpreter.",
+            interpreter.display(),
+            self.missing.join(", ")
+        )))
+    }
+}
+
+/// The interpreter of the virtualenv or conda env at `env_dir`.
+fn interpreter_in(env_dir: &Path) -> Option<PathBuf> {
+    let candidates: &[&str] = if cfg!(windows) {
+        &["Scripts/python.exe", "python.exe"]
+    } else {
+        &["bin/python3", "bin/python"]
+    };
+    candidates
+        .iter()
+        .map(|name| env_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Run the probe with `interpreter`. Blocks while Python starts.
+fn probe(interpreter: &Path) -> AppResult<PythonEnv> {
+    if !interpreter.is_absolute() || !interpreter.is_file() {
+        return Err(AppError::InvalidInput(format!(
+            "{} isn't a Python interpreter",
+            interpreter.display()
+        )));
+    }
+    let out = sidecar::without_window(Command::new(interpreter).args(["-c", PROBE]))
+        .output()
+        .map_err(|e| {
+            AppError::Unavailable(format!("Could not run {}: {e}", interpreter.display()))
+        })?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(AppError::Unavailable(format!(
+            "{} failed ({}): {}",
+            interpreter.display(),
+            out.status,
+            stderr.lines().last().unwrap_or_default()
+        )));
+    }
+    serde_json::from_slice(&out.stdout).map_err(|e| {
+        AppError::Unavailable(format!(
+            "{} doesn't look like a Python interpreter: {e}",
+            interpreter.display()
+        ))
+    })
+}
+
+/// The command that runs the backend with `interpreter`, after checking
+/// that its environment has everything installed.
+pub fn server_command(interpreter: &Path) -> AppResult<Command> {
+    let env = probe(interpreter)?;
+    env.check(interpreter)?;
+    eprintln!(
+        "[python-env] Brainshape {} on Python {} at {}",
+        env.brainshape.as_deref().unwrap_or("?"),
+        env.python,
+        interpreter.display()
+    );
+    let mut command = Command::new(interpreter);
+    command.args(["-m", "brainshape.server"]);
+    Ok(command)
+}
+
+/// The interpreter the built-in backend runs with, or `None` for the
+/// bundled sidecar.
+#[tauri::command]
+pub fn get_python_interpreter(app: AppHandle) -> Option<PathBuf> {
+    settings::get(&app).python_interpreter
+}
+
+/// Save the interpreter and restart the backend if the built-in one is in
+/// use.
+fn set_interpreter(app: &AppHandle, interpreter: Option<PathBuf>) -> AppResult<()> {
+    settings::update(app, |s| s.python_interpreter = interpreter)?;
+    match backend::connection(app) {
+        connection @ Connection::Sidecar { .. } => backend::switch(app, connection),
+        Connection::External { .. } => Ok(()),
+    }
+}
+
+/// Ask the user for a Python environment's folder and run the built-in
+/// backend with its interpreter. Returns what was found there, or `None`
+/// if cancelled.
+#[tauri::command]
+pub async fn choose_python_env(app: AppHandle) -> AppResult<Option<PythonEnv>> {
+    let dialog = paths::dialog(&app, "Select Python Environment".into(), &[]);
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(picked) = dialog.blocking_pick_folder() else {
+            return Ok(None);
+        };
+        let env_dir = picked
+            .into_path()
+            .map_err(|e| AppError::Internal(format!("Unusable path from the dialog: {e}")))?;
+        let interpreter = interpreter_in(&env_dir).ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "No Python interpreter in {}. Pick the environment's folder, e.g. .venv",
+                env_dir.display()
+            ))
+        })?;
+        let env = probe(&interpreter)?;
+        env.check(&interpreter)?;
+        set_interpreter(&app, Some(interpreter))?;
+        Ok(Some(env))
+    })
+    .await?
+}
+
+/// Go back to running the bundled sidecar.
+#[tauri::command]
+pub async fn clear_python_env(app: AppHandle) -> AppResult<()> {
+    tauri::async_runtime::spawn_blocking(move || set_interpreter(&app, None)).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_requirements() {
+        let env: PythonEnv = serde_json::from_str(
+            r#"{"python": "3.13.1", "brainshape": "0.0.2", "missing": ["surrealdb", "uvicorn"]}"#,
+        )
+        .unwrap();
+        let err = env.check(Path::new("/venv/bin/python")).unwrap_err();
+        assert_eq!(err.code(), "unavailable");
+        assert!(err.to_string().contains("missing surrealdb, uvicorn"));
+
+        let ready = PythonEnv {
+            missing: Vec::new(),
+            ..env
+        };
+        assert!(ready.check(Path::new("/venv/bin/python")).is_ok());
+    }
+
+    #[test]
+    fn finds_the_interpreter_in_an_env_folder() {
+        let env = std::env::temp_dir().join(format!("brainshape-pyenv-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&env);
+        assert_eq!(interpreter_in(&env), None);
+
+        let name = if cfg!(windows) {
+            "Scripts/python.exe"
+        } else {
+            "bin/python3"
+        };
+        let python = env.join(name);
+        std::fs::create_dir_all(python.parent().unwrap()).unwrap();
+        std::fs::write(&python, "").unwrap();
+        assert_eq!(interpreter_in(&env), Some(python));
+
+        let relative = probe(Path::new("python3")).unwrap_err();
+        assert_eq!(relative.code(), "invalid_input");
+        std::fs::remove_dir_all(&env).unwrap();
+    }
+}
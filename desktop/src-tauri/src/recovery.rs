@@ -0,0 +1,244 @@
+//! Crash recovery for unsaved editor state.
+//!
+//! A `session.lock` file marks a running session and is removed on clean
+//! exit. While the app runs, Rust periodically emits
+//! `recovery-snapshot-requested`; the frontend answers with
+//! `save_recovery_snapshot`, which overwrites this session's snapshot (or
+//! deletes it when there is nothing unsaved). Snapshots left behind by a
+//! crashed session are offered back through `get_recovery_snapshots`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::chaos;
+use crate::error::{AppError, AppResult};
+
+/// How often the frontend is asked for a snapshot.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Snapshots older than this are deleted on startup.
+const MAX_SNAPSHOT_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+const LOCK_FILE: &str = "session.lock";
+
+pub struct RecoveryState {
+    dir: PathBuf,
+    session_id: String,
+}
+
+impl RecoveryState {
+    fn snapshot_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SnapshotInfo {
+    id: String,
+    /// Unix timestamp in seconds of the last write.
+    saved_at: u64,
+    bytes: u64,
+}
+
+/// Snapshot ids are session start timestamps; reject anything else so an id
+/// can never name a file outside the recovery directory.
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Delete snapshots older than `max_age` and return the remaining ones,
+/// excluding the current session's.
+fn collect_snapshots(dir: &Path, current: &str, max_age: Duration) -> Vec<SnapshotInfo> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let now = SystemTime::now();
+    let mut snapshots = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(id) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .filter(|id| valid_id(id) && path.extension().is_some_and(|e| e == "json"))
+        else {
+            continue;
+        };
+        if id == current {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else { continue };
+        let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+        if now.duration_since(modified).unwrap_or_default() > max_age {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+        snapshots.push(SnapshotInfo {
+            id: id.to_string(),
+            saved_at: modified
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            bytes: meta.len(),
+        });
+    }
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.saved_at));
+    snapshots
+}
+
+/// Take the session lock, prune old snapshots, and start the snapshot timer.
+pub fn init(app: &AppHandle) {
+    let Ok(dir) = app.path().app_data_dir().map(|d| d.join("recovery")) else {
+        eprintln!("[recovery] Could not resolve app data directory; crash recovery disabled");
+        return;
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("[recovery] Failed to create {}: {}", dir.display(), e);
+        return;
+    }
+
+    let lock = dir.join(LOCK_FILE);
+    if lock.exists() {
+        eprintln!("[recovery] Previous session did not shut down cleanly");
+    }
+    let session_id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+        .to_string();
+    if let Err(e) = fs::write(&lock, &session_id) {
+        eprintln!("[recovery] Failed to write {}: {}", lock.display(), e);
+    }
+
+    let leftover = collect_snapshots(&dir, &session_id, MAX_SNAPSHOT_AGE);
+    if !leftover.is_empty() {
+        eprintln!(
+            "[recovery] {} snapshot(s) available to restore",
+            leftover.len()
+        );
+    }
+
+    app.manage(RecoveryState { dir, session_id });
+
+    let handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(SNAPSHOT_INTERVAL);
+        let _ = chaos::emit(&handle, "recovery-snapshot-requested", ());
+    });
+}
+
+/// Clean exit: nothing to recover, so drop this session's snapshot and lock.
+pub fn shutdown(app: &AppHandle) {
+    let Some(state) = app.try_state::<RecoveryState>() else {
+        return;
+    };
+    let _ = fs::remove_file(state.snapshot_path(&state.session_id));
+    let _ = fs::remove_file(state.dir.join(LOCK_FILE));
+}
+
+/// Delete every snapshot, including this session's.
+pub fn discard_all(app: &AppHandle) {
+    let Some(state) = app.try_state::<RecoveryState>() else {
+        return;
+    };
+    for snapshot in collect_snapshots(&state.dir, &state.session_id, MAX_SNAPSHOT_AGE) {
+        let _ = fs::remove_file(state.snapshot_path(&snapshot.id));
+    }
+    let _ = fs::remove_file(state.snapshot_path(&state.session_id));
+}
+
+/// Store the frontend's unsaved state for this session. A snapshot with an
+/// empty `unsaved` map removes the file, since there is nothing to recover.
+#[tauri::command]
+pub fn save_recovery_snapshot(
+    app: AppHandle,
+    state: State<'_, RecoveryState>,
+    snapshot: serde_json::Value,
+) -> AppResult<()> {
+    let path = state.snapshot_path(&state.session_id);
+    let empty = snapshot
+        .get("unsaved")
+        .and_then(|u| u.as_object())
+        .is_some_and(|u| u.is_empty());
+    if empty {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+    chaos::check_disk(&app)?;
+    let text = serde_json::to_string(&snapshot)?;
+    // Write then rename so a crash mid-write never leaves a truncated snapshot.
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, text)?;
+    Ok(fs::rename(&tmp, &path)?)
+}
+
+/// List snapshots left by earlier sessions, newest first.
+#[tauri::command]
+pub fn get_recovery_snapshots(state: State<'_, RecoveryState>) -> Vec<SnapshotInfo> {
+    collect_snapshots(&state.dir, &state.session_id, MAX_SNAPSHOT_AGE)
+}
+
+fn check_id(id: &str) -> AppResult<()> {
+    if valid_id(id) {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!("Invalid snapshot id: {id}")))
+    }
+}
+
+/// Return a snapshot's contents. The file is kept until it is discarded, so
+/// a failure while restoring doesn't lose it.
+#[tauri::command]
+pub fn restore_snapshot(
+    state: State<'_, RecoveryState>,
+    id: String,
+) -> AppResult<serde_json::Value> {
+    check_id(&id)?;
+    let text = fs::read_to_string(state.snapshot_path(&id))?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+#[tauri::command]
+pub fn discard_recovery_snapshot(state: State<'_, RecoveryState>, id: String) -> AppResult<()> {
+    check_id(&id)?;
+    Ok(fs::remove_file(state.snapshot_path(&id))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_numeric_ids() {
+        assert!(valid_id("1739000000000"));
+        assert!(!valid_id(""));
+        assert!(!valid_id("../settings"));
+        assert!(!valid_id("12.json"));
+    }
+
+    #[test]
+    fn collects_other_sessions_and_prunes_old() {
+        let dir = std::env::temp_dir().join(format!("brainshape-recovery-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("100.json"), "{}").unwrap();
+        fs::write(dir.join("200.json"), "{}").unwrap();
+        fs::write(dir.join(LOCK_FILE), "200").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let found = collect_snapshots(&dir, "200", MAX_SNAPSHOT_AGE);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "100");
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(collect_snapshots(&dir, "200", Duration::ZERO).is_empty());
+        assert!(!dir.join("100.json").exists());
+        assert!(dir.join("200.json").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+//! Linux sandbox detection (Flatpak and Snap).
+//!
+//! A sandboxed app can only open files outside its own directories if the
+//! user chose them through the desktop portal, which then exposes them at a
+//! stable `/run/user/<uid>/doc/...` path that stays granted across restarts.
+//! So [`prepare`] makes GTK's file dialogs go through the portal, and the
+//! frontend steers users to Browse instead of typed paths. App data, cache
+//! (and so the workspace and the sidecar's `TMPDIR`) already follow the
+//! sandbox's XDG directories. AppImage isn't sandboxed and needs nothing.
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sandbox {
+    None,
+    Flatpak,
+    Snap,
+}
+
+/// Detect the sandbox from `/.flatpak-info` and the environment.
+fn detect_with(flatpak_info: bool, var: impl Fn(&str) -> Option<String>) -> Sandbox {
+    if flatpak_info || var("FLATPAK_ID").is_some() {
+        Sandbox::Flatpak
+    } else if var("SNAP").is_some() {
+        Sandbox::Snap
+    } else {
+        Sandbox::None
+    }
+}
+
+pub fn detect() -> Sandbox {
+    if !cfg!(target_os = "linux") {
+        return Sandbox::None;
+    }
+    detect_with(std::path::Path::new("/.flatpak-info").exists(), |name| {
+        std::env::var(name).ok()
+    })
+}
+
+/// Call before the webview starts, while no other threads read the
+/// environment.
+pub fn prepare() {
+    let sandbox = detect();
+    if sandbox == Sandbox::None {
+        return;
+    }
+    eprintln!("[sandbox] Running under {:?}", sandbox);
+    if std::env::var_os("GTK_USE_PORTAL").is_none() {
+        std::env::set_var("GTK_USE_PORTAL", "1");
+    }
+}
+
+#[tauri::command]
+pub fn get_sandbox() -> Sandbox {
+    detect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_flatpak_and_snap() {
+        assert_eq!(detect_with(true, |_| None), Sandbox::Flatpak);
+        assert_eq!(
+            detect_with(false, |name| (name == "SNAP").then(|| "/snap/x/1".into())),
+            Sandbox::Snap
+        );
+        assert_eq!(detect_with(false, |_| None), Sandbox::None);
+    }
+}
@@ -0,0 +1,116 @@
+//! Preferences owned by the Rust shell rather than the backend (which keeps
+//! its own in `~/.config/brainshape/settings.json`), persisted to
+//! `desktop-settings.json` in the app data directory.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::connections::SavedConnection;
+use crate::error::{AppResult, LockExt};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesktopSettings {
+    /// Global shortcut per action name. `None` disables an action that has
+    /// a default; actions missing here use their default.
+    pub shortcuts: BTreeMap<String, Option<String>>,
+    /// Feature flag overrides by flag id; see `flags.rs`.
+    pub feature_flags: BTreeMap<String, bool>,
+    /// Saved backends; see `connections.rs`.
+    pub connections: Vec<SavedConnection>,
+    /// Saved connection to use at launch instead of the built-in backend.
+    pub default_connection: Option<String>,
+    /// Python interpreter to run the built-in backend with instead of the
+    /// bundled sidecar; see `python_env.rs`.
+    pub python_interpreter: Option<PathBuf>,
+}
+
+pub struct SettingsStore {
+    settings: DesktopSettings,
+    path: Option<PathBuf>,
+}
+
+impl SettingsStore {
+    /// Load from `path`, falling back to defaults if it is missing or corrupt.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let settings = match path.as_ref().map(fs::read_to_string) {
+            Some(Ok(text)) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                eprintln!("[settings] Ignoring corrupt desktop settings: {}", e);
+                DesktopSettings::default()
+            }),
+            _ => DesktopSettings::default(),
+        };
+        Self { settings, path }
+    }
+
+    fn save(&self) -> AppResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = serde_json::to_string_pretty(&self.settings)?;
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    let path = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("desktop-settings.json"))
+        .ok();
+    app.manage(Mutex::new(SettingsStore::load(path)));
+}
+
+/// Current settings (defaults if `init` hasn't run).
+pub fn get(app: &AppHandle) -> DesktopSettings {
+    app.try_state::<Mutex<SettingsStore>>()
+        .map(|store| store.lock_or_recover().settings.clone())
+        .unwrap_or_default()
+}
+
+/// Apply `change` and persist the result.
+pub fn update(app: &AppHandle, change: impl FnOnce(&mut DesktopSettings)) -> AppResult<()> {
+    let store = app.state::<Mutex<SettingsStore>>();
+    let mut store = store.lock_or_recover();
+    change(&mut store.settings);
+    store.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_tolerates_corruption() {
+        let path = std::env::temp_dir().join(format!(
+            "brainshape-desktop-settings-{}.json",
+            std::process::id()
+        ));
+        let mut store = SettingsStore::load(Some(path.clone()));
+        store
+            .settings
+            .shortcuts
+            .insert("toggle_window".into(), None);
+        store.save().unwrap();
+        assert_eq!(
+            SettingsStore::load(Some(path.clone())).settings,
+            store.settings
+        );
+
+        fs::write(&path, "{not json").unwrap();
+        assert_eq!(
+            SettingsStore::load(Some(path.clone())).settings,
+            DesktopSettings::default()
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
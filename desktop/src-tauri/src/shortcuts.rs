@@ -0,0 +1,288 @@
+//! System-wide keyboard shortcuts.
+//!
+//! Each [`Action`] can be bound to one accelerator (e.g.
+//! `CommandOrControl+Alt+N`). Bindings are stored in the desktop settings;
+//! an action without a stored binding uses its default. Two actions can't
+//! share an accelerator, and a binding the OS refuses (usually because
+//! another app holds it) is reported back instead of silently dropped.
+//!
+//! A pressed shortcut runs the matching entry in the action registry.
+
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::error::{AppError, AppResult, LockExt};
+use crate::{actions, settings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Show and focus the window, or hide it if it's already focused.
+    ToggleWindow,
+    NewNote,
+    Search,
+}
+
+impl Action {
+    const ALL: [Action; 3] = [Self::ToggleWindow, Self::NewNote, Self::Search];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ToggleWindow => "toggle_window",
+            Self::NewNote => "new_note",
+            Self::Search => "search",
+        }
+    }
+
+    /// Id in the action registry.
+    fn action_id(self) -> &'static str {
+        match self {
+            Self::ToggleWindow => "toggle-window",
+            Self::NewNote => "new-note",
+            Self::Search => "view-search",
+        }
+    }
+
+    fn default_accelerator(self) -> Option<&'static str> {
+        match self {
+            Self::ToggleWindow => Some("CommandOrControl+Alt+B"),
+            Self::NewNote => Some("CommandOrControl+Alt+N"),
+            Self::Search => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ShortcutStatus {
+    action: Action,
+    accelerator: Option<String>,
+    registered: bool,
+    /// Why registration failed, e.g. another application holds the shortcut.
+    error: Option<String>,
+}
+
+/// Bindings currently held with the OS.
+#[derive(Default)]
+struct Registered {
+    /// False if the plugin failed to start (e.g. no X11 on Wayland-only Linux).
+    available: bool,
+    bindings: Vec<(Action, Shortcut)>,
+    statuses: Vec<ShortcutStatus>,
+}
+
+/// Binding for every action: stored value, else the default.
+fn configured(stored: &settings::DesktopSettings) -> Vec<(Action, Option<String>)> {
+    Action::ALL
+        .iter()
+        .map(|&action| {
+            let accelerator = match stored.shortcuts.get(action.name()) {
+                Some(value) => value.clone(),
+                None => action.default_accelerator().map(String::from),
+            };
+            (action, accelerator)
+        })
+        .collect()
+}
+
+/// Parse every binding and reject duplicates, returning the first problem.
+fn validate(bindings: &[(Action, Option<String>)]) -> Result<Vec<(Action, Shortcut)>, String> {
+    let mut parsed: Vec<(Action, Shortcut)> = Vec::new();
+    for (action, accelerator) in bindings {
+        let Some(accelerator) = accelerator else {
+            continue;
+        };
+        let shortcut = Shortcut::from_str(accelerator)
+            .map_err(|e| format!("Invalid shortcut {accelerator}: {e}"))?;
+        if let Some((other, _)) = parsed.iter().find(|(_, s)| *s == shortcut) {
+            return Err(format!("{accelerator} is already used by {}", other.name()));
+        }
+        parsed.push((*action, shortcut));
+    }
+    Ok(parsed)
+}
+
+fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let action = app
+        .state::<Mutex<Registered>>()
+        .lock_or_recover()
+        .bindings
+        .iter()
+        .find(|(_, s)| s == shortcut)
+        .map(|(action, _)| *action);
+    if let Some(action) = action {
+        if let Err(e) = actions::invoke(app, action.action_id()) {
+            eprintln!("[shortcuts] {}", e);
+        }
+    }
+}
+
+/// Replace all OS registrations with `bindings`, recording per-action status.
+fn apply(app: &AppHandle, bindings: &[(Action, Option<String>)], parsed: Vec<(Action, Shortcut)>) {
+    let state = app.state::<Mutex<Registered>>();
+    let mut registered = state.lock_or_recover();
+    if !registered.available {
+        registered.statuses = bindings
+            .iter()
+            .map(|(action, accelerator)| ShortcutStatus {
+                action: *action,
+                accelerator: accelerator.clone(),
+                registered: false,
+                error: Some("Global shortcuts are unavailable on this system".into()),
+            })
+            .collect();
+        return;
+    }
+    let global = app.global_shortcut();
+    for (_, shortcut) in registered.bindings.drain(..) {
+        let _ = global.unregister(shortcut);
+    }
+
+    let mut statuses = Vec::new();
+    for (action, accelerator) in bindings {
+        let mut status = ShortcutStatus {
+            action: *action,
+            accelerator: accelerator.clone(),
+            registered: false,
+            error: None,
+        };
+        if let Some((_, shortcut)) = parsed.iter().find(|(a, _)| a == action) {
+            match global.register(*shortcut) {
+                Ok(()) => {
+                    status.registered = true;
+                    registered.bindings.push((*action, *shortcut));
+                }
+                Err(e) => {
+                    eprintln!(
+                        "[shortcuts] Could not register {} for {}: {}",
+                        accelerator.as_deref().unwrap_or_default(),
+                        action.name(),
+                        e
+                    );
+                    status.error = Some(e.to_string());
+                }
+            }
+        }
+        statuses.push(status);
+    }
+    registered.statuses = statuses;
+}
+
+/// Install the plugin and register the stored (or default) bindings.
+pub fn init(app: &AppHandle) {
+    app.manage(Mutex::new(Registered::default()));
+    let plugin = tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(on_shortcut)
+        .build();
+    match app.plugin(plugin) {
+        Ok(()) => app.state::<Mutex<Registered>>().lock_or_recover().available = true,
+        Err(e) => eprintln!("[shortcuts] Global shortcuts unavailable: {}", e),
+    }
+
+    let bindings = configured(&settings::get(app));
+    match validate(&bindings) {
+        Ok(parsed) => apply(app, &bindings, parsed),
+        Err(e) => {
+            // A hand-edited settings file; fall back to defaults.
+            eprintln!("[shortcuts] {}; using defaults", e);
+            let defaults = configured(&settings::DesktopSettings::default());
+            if let Ok(parsed) = validate(&defaults) {
+                apply(app, &defaults, parsed);
+            }
+        }
+    }
+}
+
+/// Drop every stored binding and register the defaults.
+pub fn reset(app: &AppHandle) -> AppResult<()> {
+    settings::update(app, |s| s.shortcuts.clear())?;
+    let defaults = configured(&settings::DesktopSettings::default());
+    let parsed = validate(&defaults).map_err(AppError::Internal)?;
+    apply(app, &defaults, parsed);
+    Ok(())
+}
+
+/// Accelerator currently registered for a registry action.
+pub fn accelerator_for(app: &AppHandle, action_id: &str) -> Option<String> {
+    let state = app.try_state::<Mutex<Registered>>()?;
+    let registered = state.lock_or_recover();
+    registered
+        .statuses
+        .iter()
+        .find(|s| s.registered && s.action.action_id() == action_id)
+        .and_then(|s| s.accelerator.clone())
+}
+
+#[tauri::command]
+pub fn get_shortcuts(app: AppHandle) -> Vec<ShortcutStatus> {
+    app.state::<Mutex<Registered>>()
+        .lock_or_recover()
+        .statuses
+        .clone()
+}
+
+/// Bind `action` to `accelerator`, or disable it when `accelerator` is
+/// omitted. Invalid or duplicate bindings are rejected before anything is
+/// changed; OS-level conflicts show up in the returned statuses.
+#[tauri::command]
+pub fn set_shortcut(
+    app: AppHandle,
+    action: Action,
+    accelerator: Option<String>,
+) -> AppResult<Vec<ShortcutStatus>> {
+    let accelerator = accelerator.filter(|a| !a.trim().is_empty());
+    let mut stored = settings::get(&app);
+    stored
+        .shortcuts
+        .insert(action.name().to_string(), accelerator.clone());
+    let bindings = configured(&stored);
+    let parsed = validate(&bindings).map_err(AppError::InvalidInput)?;
+
+    settings::update(&app, |s| {
+        s.shortcuts.insert(action.name().to_string(), accelerator);
+    })?;
+    apply(&app, &bindings, parsed);
+    Ok(get_shortcuts(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        let bindings = configured(&settings::DesktopSettings::default());
+        assert_eq!(validate(&bindings).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn stored_bindings_override_defaults() {
+        let mut stored = settings::DesktopSettings::default();
+        stored.shortcuts.insert("toggle_window".into(), None);
+        stored
+            .shortcuts
+            .insert("search".into(), Some("CommandOrControl+Alt+F".into()));
+        let bindings = configured(&stored);
+        assert_eq!(bindings[0], (Action::ToggleWindow, None));
+        assert_eq!(
+            bindings[2],
+            (Action::Search, Some("CommandOrControl+Alt+F".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_duplicates_and_garbage() {
+        let duplicate = [
+            (Action::NewNote, Some("Alt+N".to_string())),
+            (Action::Search, Some("alt+n".to_string())),
+        ];
+        assert!(validate(&duplicate).unwrap_err().contains("new_note"));
+        assert!(validate(&[(Action::Search, Some("Hyper+Banana".into()))]).is_err());
+    }
+}
@@ -0,0 +1,340 @@
+//! The backend child process: spawning, readiness, exit detection, and
+//! shutdown. Kept free of Tauri so `tests/sidecar.rs` can drive it against
+//! a fake server; `backend.rs` wires it into the app.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::error::{AppError, AppResult};
+
+/// Called with each line the sidecar prints, and whether it came from stderr.
+pub type OutputHandler = Arc<dyn Fn(&str, bool) + Send + Sync>;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Process creation flags; a GUI app's console child otherwise flashes a
+// console window on Windows.
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+#[cfg(windows)]
+const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Keep `command`, a console program, from flashing a window on Windows.
+pub fn without_window(command: &mut Command) -> &mut Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
+
+/// A snapshot of a sidecar's bind address; see [`Sidecar::loopback_check`].
+pub struct LoopbackCheck {
+    reported_host: Option<String>,
+    port: u16,
+}
+
+impl LoopbackCheck {
+    pub fn run(self) -> AppResult<()> {
+        if let Some(host) = self.reported_host.filter(|host| !is_loopback_host(host)) {
+            return Err(exposed(&host));
+        }
+        match reachable_off_loopback(self.port) {
+            Some(ip) => Err(exposed(&ip.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct Sidecar {
+    child: Child,
+    port: u16,
+    /// Host from the server's "running on http://..." line, once printed.
+    reported_host: Arc<Mutex<Option<String>>>,
+}
+
+impl Sidecar {
+    /// Spawn `command` (which should make the server listen on `port` on
+    /// loopback), forwarding its output to `on_output`.
+    pub fn spawn(command: Command, port: u16, on_output: OutputHandler) -> AppResult<Self> {
+        Self::start(command, port, Some(on_output))
+    }
+
+    /// Spawn `command` with its own console for troubleshooting: a new
+    /// console window on Windows, elsewhere the terminal the app runs in.
+    pub fn spawn_in_console(command: Command, port: u16) -> AppResult<Self> {
+        Self::start(command, port, None)
+    }
+
+    fn start(mut command: Command, port: u16, on_output: Option<OutputHandler>) -> AppResult<Self> {
+        let program = command.get_program().to_string_lossy().into_owned();
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(if on_output.is_some() {
+                CREATE_NO_WINDOW
+            } else {
+                CREATE_NEW_CONSOLE
+            });
+        }
+        let output = || match on_output {
+            Some(_) => Stdio::piped(),
+            None => Stdio::inherit(),
+        };
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(output())
+            .stderr(output())
+            .spawn()
+            .map_err(|e| AppError::Internal(format!("Could not start {program}: {e}")))?;
+        let reported_host = Arc::new(Mutex::new(None));
+        if let Some(on_output) = on_output {
+            let host = reported_host.clone();
+            let on_output: OutputHandler = Arc::new(move |line, stderr| {
+                if let Some(reported) = reported_bind_host(line) {
+                    *host.lock().unwrap_or_else(|e| e.into_inner()) = Some(reported);
+                }
+                on_output(line, stderr);
+            });
+            if let Some(stdout) = child.stdout.take() {
+                forward(stdout, on_output.clone(), false);
+            }
+            if let Some(stderr) = child.stderr.take() {
+                forward(stderr, on_output, true);
+            }
+        }
+        Ok(Self {
+            child,
+            port,
+            reported_host,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The exit status, if the process has exited.
+    pub fn exited(&mut self) -> Option<ExitStatus> {
+        self.child.try_wait().ok().flatten()
+    }
+
+    /// Block until `/health` answers, failing early if the process exits.
+    pub fn wait_until_ready(&mut self, timeout: Duration) -> AppResult<()> {
+        let port = self.port;
+        wait_until_ready(port, timeout, || match self.exited() {
+            Some(status) => Err(exited_during_startup(status)),
+            None => Ok(()),
+        })
+    }
+
+    /// Fail if the server is reachable from other machines: it reported a
+    /// non-loopback bind address, or it answers on this machine's network
+    /// address. Call once it is ready.
+    pub fn check_loopback_only(&self) -> AppResult<()> {
+        self.loopback_check().run()
+    }
+
+    /// What [`Sidecar::check_loopback_only`] needs, to run it without
+    /// holding on to the sidecar (it can block on a TCP connect).
+    pub fn loopback_check(&self) -> LoopbackCheck {
+        LoopbackCheck {
+            reported_host: self
+                .reported_host
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            port: self.port,
+        }
+    }
+
+    /// Kill the process and reap it.
+    pub fn kill(&mut self) {
+        if self.exited().is_none() {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for Sidecar {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// The machine the sidecar will run on. Under Rosetta this differs from the
+/// app's own build: an x86_64 app on Apple silicon can still start an arm64
+/// sidecar natively.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Host {
+    pub arch: &'static str,
+    /// The app itself is x86_64 code translated by Rosetta.
+    pub translated: bool,
+    pub avx2: bool,
+}
+
+impl Host {
+    pub fn detect() -> Self {
+        let translated = running_under_rosetta();
+        Self {
+            arch: if translated {
+                "aarch64"
+            } else {
+                std::env::consts::ARCH
+            },
+            translated,
+            avx2: !translated && has_avx2(),
+        }
+    }
+
+    /// Sidecar directory names to look for, most specific first: an AVX2
+    /// build, one for this architecture, then the bundle's default.
+    pub fn variants(&self) -> Vec<String> {
+        let mut variants = Vec::new();
+        if self.arch == "x86_64" && self.avx2 {
+            variants.push("brainshape-server-x86_64-avx2".to_string());
+        }
+        variants.push(format!("brainshape-server-{}", self.arch));
+        variants.push("brainshape-server".to_string());
+        variants
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn running_under_rosetta() -> bool {
+    use std::ffi::{c_char, c_int, c_void};
+
+    extern "C" {
+        fn sysctlbyname(
+            name: *const c_char,
+            oldp: *mut c_void,
+            oldlenp: *mut usize,
+            newp: *mut c_void,
+            newlen: usize,
+        ) -> c_int;
+    }
+    let mut value: c_int = 0;
+    let mut size = std::mem::size_of::<c_int>();
+    // SAFETY: the name is NUL-terminated and `value`/`size` describe a c_int.
+    let status = unsafe {
+        sysctlbyname(
+            c"sysctl.proc_translated".as_ptr(),
+            (&mut value as *mut c_int).cast(),
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    status == 0 && value == 1
+}
+
+#[cfg(not(target_os = "macos"))]
+fn running_under_rosetta() -> bool {
+    false
+}
+
+#[cfg(target_arch = "x86_64")]
+fn has_avx2() -> bool {
+    std::arch::is_x86_feature_detected!("avx2")
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn has_avx2() -> bool {
+    false
+}
+
+fn exposed(host: &str) -> AppError {
+    AppError::Unavailable(format!(
+        "The backend is listening on {host} instead of only 127.0.0.1, which would expose your notes and API keys to the network. Brainshape stopped it."
+    ))
+}
+
+/// The host from a "running on http://HOST:PORT" or "listening on
+/// http://HOST:PORT" line, as uvicorn prints at startup.
+pub fn reported_bind_host(line: &str) -> Option<String> {
+    let (_, rest) = line
+        .split_once("running on http://")
+        .or_else(|| line.split_once("listening on http://"))?;
+    let authority = rest.split(['/', ' ']).next()?;
+    let (host, _port) = authority.rsplit_once(':')?;
+    Some(host.trim_matches(['[', ']']).to_string())
+}
+
+pub fn is_loopback_host(host: &str) -> bool {
+    host == "localhost"
+        || host.parse::<Ipv4Addr>().is_ok_and(|ip| ip.is_loopback())
+        || host.parse::<Ipv6Addr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// This machine's outward-facing address, if the server on `port` accepts
+/// connections on it.
+fn reachable_off_loopback(port: u16) -> Option<IpAddr> {
+    // Connecting a UDP socket only picks a route; no packet is sent.
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9)).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    if ip.is_loopback() || ip.is_unspecified() {
+        return None;
+    }
+    TcpStream::connect_timeout(&SocketAddr::new(ip, port), HEALTH_TIMEOUT)
+        .ok()
+        .map(|_| ip)
+}
+
+pub fn exited_during_startup(status: ExitStatus) -> AppError {
+    AppError::Unavailable(format!("The backend exited during startup ({status})"))
+}
+
+/// Poll `/health` on `port` until it answers 200, `timeout` passes, or
+/// `check` (run between attempts, e.g. to notice the process exiting)
+/// returns an error.
+pub fn wait_until_ready(
+    port: u16,
+    timeout: Duration,
+    mut check: impl FnMut() -> AppResult<()>,
+) -> AppResult<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        check()?;
+        if is_healthy(port) {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(AppError::Unavailable(format!(
+                "The backend didn't respond within {} seconds",
+                timeout.as_secs()
+            )));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Whether a server on `port` answers `GET /health` with 200.
+pub fn is_healthy(port: u16) -> bool {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, HEALTH_TIMEOUT) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(HEALTH_TIMEOUT));
+    let request = "GET /health HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n";
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+    let mut status_line = String::new();
+    let _ = BufReader::new(stream).read_line(&mut status_line);
+    status_line.split_whitespace().nth(1) == Some("200")
+}
+
+fn forward(stream: impl Read + Send + 'static, on_output: OutputHandler, stderr: bool) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            on_output(&line, stderr);
+        }
+    });
+}
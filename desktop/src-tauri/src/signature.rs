@@ -0,0 +1,114 @@
+//! Code-signature check of the sidecar before it runs.
+//!
+//! The sidecar must be signed by the same developer as the app itself (team
+//! ID on macOS, certificate subject on Windows), so a binary swapped into the
+//! install can't run with the app's trust. An app that isn't signed by a
+//! developer (a local build) accepts any sidecar, since its own binary is no
+//! better protected. Linux has no platform code signing, so nothing is
+//! checked there. `--allow-unsigned-backend` skips the check.
+
+use std::path::Path;
+
+use crate::error::{AppError, AppResult};
+
+/// Refuse `sidecar` unless its signer matches the app's.
+fn check(app_signer: Option<&str>, sidecar_signer: Option<&str>, sidecar: &Path) -> AppResult<()> {
+    match (app_signer, sidecar_signer) {
+        (None, _) => Ok(()),
+        (Some(app), Some(sidecar_signer)) if app == sidecar_signer => Ok(()),
+        (Some(app), found) => Err(AppError::PermissionDenied(format!(
+            "The backend at {} is {}, not by {} like Brainshape",
+            sidecar.display(),
+            found.map_or("not signed".to_string(), |s| format!("signed by {s}")),
+            app
+        ))),
+    }
+}
+
+pub fn verify(sidecar: &Path) -> AppResult<()> {
+    let app = std::env::current_exe()?;
+    check(
+        platform::signer(&app).as_deref(),
+        platform::signer(sidecar).as_deref(),
+        sidecar,
+    )
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::path::Path;
+    use std::process::Command;
+
+    /// Team ID of a valid signature, from `codesign`.
+    pub fn signer(path: &Path) -> Option<String> {
+        let valid = Command::new("/usr/bin/codesign")
+            .args(["--verify", "--strict"])
+            .arg(path)
+            .output()
+            .is_ok_and(|out| out.status.success());
+        if !valid {
+            return None;
+        }
+        let out = Command::new("/usr/bin/codesign")
+            .args(["--display", "--verbose=2"])
+            .arg(path)
+            .output()
+            .ok()?;
+        // codesign prints the details on stderr.
+        String::from_utf8_lossy(&out.stderr)
+            .lines()
+            .find_map(|line| line.strip_prefix("TeamIdentifier="))
+            .filter(|team| *team != "not set")
+            .map(str::to_string)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::path::Path;
+    use std::process::Command;
+
+    /// Certificate subject of a valid Authenticode signature.
+    pub fn signer(path: &Path) -> Option<String> {
+        let script = "$s = Get-AuthenticodeSignature -LiteralPath $env:BRAINSHAPE_SIGNED_PATH; \
+                      if ($s.Status -eq 'Valid') { $s.SignerCertificate.Subject }";
+        let out = crate::sidecar::without_window(
+            Command::new("powershell")
+                .args(["-NoProfile", "-NonInteractive", "-Command", script])
+                .env("BRAINSHAPE_SIGNED_PATH", path),
+        )
+        .output()
+        .ok()?;
+        let subject = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        (out.status.success() && !subject.is_empty()).then_some(subject)
+    }
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+mod platform {
+    use std::path::Path;
+
+    pub fn signer(_path: &Path) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidecar_must_match_a_signed_app() {
+        let path = Path::new("brainshape-server");
+        assert!(check(None, None, path).is_ok());
+        assert!(check(Some("TEAM1"), Some("TEAM1"), path).is_ok());
+
+        let foreign = check(Some("TEAM1"), Some("TEAM2"), path).unwrap_err();
+        assert_eq!(foreign.code(), "permission_denied");
+        assert!(foreign.to_string().contains("signed by TEAM2"));
+        assert!(check(Some("TEAM1"), None, path)
+            .unwrap_err()
+            .to_string()
+            .contains("not signed"));
+    }
+}
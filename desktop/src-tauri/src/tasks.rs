@@ -0,0 +1,231 @@
+//! Background tasks: long-running work the frontend can watch and cancel.
+//!
+//! A command hands its blocking work to [`run`], which registers a task,
+//! runs it on the blocking thread pool, and removes it once it finishes.
+//! The work gets a [`Task`] to report progress on and to check between
+//! steps; `cancel_task` only sets a flag, so the work stops at its next
+//! [`Task::check`] and cleans up after itself like after any other error.
+//! Every change is published as `tasks-changed` with the full list,
+//! throttled so a fast task can't flood the webview.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::{AppError, AppResult, LockExt};
+use crate::throttle;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TaskInfo {
+    id: u64,
+    /// What kind of work this is, e.g. `import_archive`.
+    kind: &'static str,
+    /// Shown to the user, e.g. the archive's file name.
+    label: String,
+    done: u64,
+    /// `0` while the amount of work isn't known.
+    total: u64,
+    cancelling: bool,
+}
+
+struct Entry {
+    info: TaskInfo,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+pub struct Tasks {
+    next_id: u64,
+    entries: BTreeMap<u64, Entry>,
+}
+
+impl Tasks {
+    fn list(&self) -> Vec<TaskInfo> {
+        self.entries.values().map(|e| e.info.clone()).collect()
+    }
+
+    /// Add a task and return its id and cancellation flag.
+    fn register(&mut self, kind: &'static str, label: String) -> (u64, Arc<AtomicBool>) {
+        self.next_id += 1;
+        let id = self.next_id;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.entries.insert(
+            id,
+            Entry {
+                info: TaskInfo {
+                    id,
+                    kind,
+                    label,
+                    done: 0,
+                    total: 0,
+                    cancelling: false,
+                },
+                cancelled: cancelled.clone(),
+            },
+        );
+        (id, cancelled)
+    }
+
+    /// Record progress; `false` once the task has finished.
+    fn progress(&mut self, id: u64, done: u64, total: u64) -> bool {
+        let Some(entry) = self.entries.get_mut(&id) else {
+            return false;
+        };
+        entry.info.done = done;
+        entry.info.total = total;
+        true
+    }
+
+    /// Remove a task that has finished, with its progress.
+    fn finish(&mut self, id: u64) {
+        self.entries.remove(&id);
+    }
+
+    /// Flag the task as cancelled; `false` if it already was.
+    fn cancel(&mut self, id: u64) -> AppResult<bool> {
+        let entry = self
+            .entries
+            .get_mut(&id)
+            .ok_or_else(|| AppError::NotFound(format!("No running task {id}")))?;
+        if entry.info.cancelling {
+            return Ok(false);
+        }
+        entry.cancelled.store(true, Ordering::Relaxed);
+        entry.info.cancelling = true;
+        Ok(true)
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(Mutex::new(Tasks::default()));
+}
+
+fn emit(app: &AppHandle, tasks: &Tasks) {
+    throttle::emit_latest(app, "tasks-changed", tasks.list());
+}
+
+/// Handle given to a task's work.
+pub struct Task {
+    app: AppHandle,
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Task {
+    /// Record progress; call as often as is convenient.
+    pub fn progress(&self, done: u64, total: u64) {
+        let state = self.app.state::<Mutex<Tasks>>();
+        let mut tasks = state.lock_or_recover();
+        if tasks.progress(self.id, done, total) {
+            emit(&self.app, &tasks);
+        }
+    }
+
+    /// Fail with `Interrupted` once the task has been cancelled.
+    pub fn check(&self) -> io::Result<()> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A task label naming the file at `path`.
+pub fn file_label(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Run `work` as a task on the blocking thread pool and return its result.
+pub async fn run<T, F>(app: &AppHandle, kind: &'static str, label: String, work: F) -> AppResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Task) -> AppResult<T> + Send + 'static,
+{
+    let (id, cancelled) = {
+        let state = app.state::<Mutex<Tasks>>();
+        let mut tasks = state.lock_or_recover();
+        let registered = tasks.register(kind, label);
+        emit(app, &tasks);
+        registered
+    };
+
+    let task = Task {
+        app: app.clone(),
+        id,
+        cancelled,
+    };
+    let result = tauri::async_runtime::spawn_blocking(move || work(&task)).await;
+
+    let state = app.state::<Mutex<Tasks>>();
+    let mut tasks = state.lock_or_recover();
+    tasks.finish(id);
+    emit(app, &tasks);
+    result?
+}
+
+/// Tasks that are still running.
+#[tauri::command]
+pub fn list_tasks(state: State<'_, Mutex<Tasks>>) -> Vec<TaskInfo> {
+    state.lock_or_recover().list()
+}
+
+/// Ask a task to stop. Its command then fails with code `cancelled`.
+/// Asking again does nothing.
+#[tauri::command]
+pub fn cancel_task(app: AppHandle, id: u64) -> AppResult<()> {
+    let state = app.state::<Mutex<Tasks>>();
+    let mut tasks = state.lock_or_recover();
+    if tasks.cancel(id)? {
+        emit(&app, &tasks);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelling_an_unknown_task_fails() {
+        let mut tasks = Tasks::default();
+        assert_eq!(tasks.cancel(7).unwrap_err().code(), "not_found");
+    }
+
+    #[test]
+    fn finished_tasks_drop_their_progress() {
+        let mut tasks = Tasks::default();
+        let (id, _) = tasks.register("import_archive", "vault.zip".into());
+        assert!(tasks.progress(id, 3, 10));
+        let info = &tasks.list()[0];
+        assert_eq!((info.done, info.total), (3, 10));
+
+        tasks.finish(id);
+        assert!(tasks.list().is_empty());
+        assert!(!tasks.progress(id, 4, 10));
+        assert!(tasks.list().is_empty());
+    }
+
+    #[test]
+    fn a_second_cancel_is_a_no_op() {
+        let mut tasks = Tasks::default();
+        let (id, cancelled) = tasks.register("create_backup", "backup.zip".into());
+        let (other, other_cancelled) = tasks.register("stage_drop", "notes".into());
+        assert_ne!(id, other);
+
+        assert!(tasks.cancel(id).unwrap());
+        assert!(cancelled.load(Ordering::Relaxed));
+        assert!(tasks.list()[0].cancelling);
+        assert!(!tasks.cancel(id).unwrap());
+        assert!(tasks.list()[0].cancelling);
+        assert!(!other_cancelled.load(Ordering::Relaxed));
+    }
+}
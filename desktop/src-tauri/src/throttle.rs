@@ -0,0 +1,143 @@
+//! Rate limiting for events that carry state.
+//!
+//! Some events describe "how things are now" (the task list, the unread
+//! count) and can fire in bursts. [`emit_latest`] sends at most one such
+//! event per [`INTERVAL`] for each name: the first of a burst goes out at
+//! once, later ones replace each other, and the latest is sent when the
+//! interval is up. So the webview never sees a flood of events, and it still
+//! always ends up with the final state. Events that each matter on their own
+//! (`files-dropped`, `deep-link`) go through `chaos::emit` directly.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::chaos;
+use crate::error::LockExt;
+
+pub const INTERVAL: Duration = Duration::from_millis(100);
+
+/// What to do with an offered payload.
+#[derive(Debug, PartialEq)]
+enum Offer {
+    /// Send it now.
+    Now(serde_json::Value),
+    /// Held back; flush it after this delay.
+    Schedule(Duration),
+    /// Held back in place of an earlier pending payload, whose flush is
+    /// already scheduled.
+    Coalesced,
+}
+
+/// Throttling state of one event name.
+#[derive(Default)]
+struct Slot {
+    last_sent: Option<Instant>,
+    pending: Option<serde_json::Value>,
+}
+
+impl Slot {
+    fn offer(&mut self, now: Instant, payload: serde_json::Value) -> Offer {
+        if self.pending.is_some() {
+            self.pending = Some(payload);
+            return Offer::Coalesced;
+        }
+        match self.last_sent.map(|at| now.saturating_duration_since(at)) {
+            Some(since) if since < INTERVAL => {
+                self.pending = Some(payload);
+                Offer::Schedule(INTERVAL - since)
+            }
+            _ => {
+                self.last_sent = Some(now);
+                Offer::Now(payload)
+            }
+        }
+    }
+
+    /// The pending payload, once its scheduled time has come.
+    fn flush(&mut self, now: Instant) -> Option<serde_json::Value> {
+        let payload = self.pending.take()?;
+        self.last_sent = Some(now);
+        Some(payload)
+    }
+}
+
+#[derive(Default)]
+pub struct Throttle(Mutex<HashMap<&'static str, Slot>>);
+
+pub fn init(app: &AppHandle) {
+    app.manage(Throttle::default());
+}
+
+/// Emit `event`, coalescing bursts so only the latest payload of each
+/// interval is sent.
+pub fn emit_latest<S: Serialize>(app: &AppHandle, event: &'static str, payload: S) {
+    let payload = match serde_json::to_value(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("[throttle] Could not serialize {}: {}", event, e);
+            return;
+        }
+    };
+    let Some(throttle) = app.try_state::<Throttle>() else {
+        let _ = chaos::emit(app, event, payload);
+        return;
+    };
+    let offer = throttle
+        .0
+        .lock_or_recover()
+        .entry(event)
+        .or_default()
+        .offer(Instant::now(), payload);
+    match offer {
+        Offer::Now(payload) => {
+            let _ = chaos::emit(app, event, payload);
+        }
+        Offer::Schedule(delay) => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                let throttle = app.state::<Throttle>();
+                let payload = throttle
+                    .0
+                    .lock_or_recover()
+                    .get_mut(event)
+                    .and_then(|slot| slot.flush(Instant::now()));
+                if let Some(payload) = payload {
+                    let _ = chaos::emit(&app, event, payload);
+                }
+            });
+        }
+        Offer::Coalesced => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn coalesces_bursts_to_the_latest_state() {
+        let start = Instant::now();
+        let mut slot = Slot::default();
+        assert_eq!(slot.offer(start, json!(1)), Offer::Now(json!(1)));
+
+        let soon = start + Duration::from_millis(30);
+        assert_eq!(
+            slot.offer(soon, json!(2)),
+            Offer::Schedule(Duration::from_millis(70))
+        );
+        assert_eq!(slot.offer(soon, json!(3)), Offer::Coalesced);
+
+        let due = start + INTERVAL;
+        assert_eq!(slot.flush(due), Some(json!(3)));
+        assert_eq!(slot.flush(due), None);
+
+        // Quiet for a full interval: the next one goes straight out.
+        assert_eq!(slot.offer(due + INTERVAL, json!(4)), Offer::Now(json!(4)));
+    }
+}
@@ -0,0 +1,282 @@
+//! SSH tunnels to backends on other machines.
+//!
+//! The backend has no authentication and only listens on loopback, so a
+//! remote one is reached through an SSH local forward. The system `ssh`
+//! forwards a port on this machine to the backend's port on the remote
+//! host's loopback, optionally through a jump host. The saved connection's
+//! URL points at the local end. Authentication is whatever `ssh` already
+//! uses (keys, the agent, `~/.ssh/config`, including `UseKeychain` on
+//! macOS), in batch mode so it never prompts. A tunnel that drops is
+//! restarted with backoff until the connection is switched away. Its state
+//! is published as `tunnel-status`.
+
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::chaos;
+use crate::error::{AppError, AppResult, LockExt};
+use crate::sidecar::{self, Sidecar};
+
+const POLL: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SshTunnel {
+    /// `user@host`, or a `Host` alias from the SSH config.
+    pub host: String,
+    /// Jump host, as for `ssh -J`.
+    #[serde(default)]
+    pub jump: Option<String>,
+    /// The backend's port on the remote host's loopback.
+    pub remote_port: u16,
+}
+
+/// A host argument that `ssh` can't mistake for an option.
+fn valid_host(host: &str) -> bool {
+    !host.is_empty() && !host.starts_with('-') && !host.contains(char::is_whitespace)
+}
+
+impl SshTunnel {
+    pub fn validate(&self) -> AppResult<()> {
+        if !valid_host(&self.host) || !self.jump.as_deref().is_none_or(valid_host) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid SSH host: {}",
+                self.host
+            )));
+        }
+        if self.remote_port == 0 {
+            return Err(AppError::InvalidInput("Choose the remote port".into()));
+        }
+        Ok(())
+    }
+
+    fn args(&self, local_port: u16) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "-N",
+            "-T",
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            "ExitOnForwardFailure=yes",
+            "-o",
+            "ServerAliveInterval=15",
+            "-o",
+            "ServerAliveCountMax=3",
+            "-L",
+        ]
+        .map(String::from)
+        .into();
+        args.push(format!(
+            "127.0.0.1:{local_port}:127.0.0.1:{}",
+            self.remote_port
+        ));
+        if let Some(jump) = &self.jump {
+            args.extend(["-J".to_string(), jump.clone()]);
+        }
+        args.extend(["--".to_string(), self.host.clone()]);
+        args
+    }
+}
+
+/// Wait before the `failures`th reconnect: 2 s, doubling up to a minute.
+fn backoff(failures: u32) -> Duration {
+    Duration::from_secs(2)
+        .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum TunnelStatus {
+    Connecting,
+    /// The backend answers through the tunnel.
+    Up,
+    Reconnecting {
+        error: String,
+        retry_in_secs: u64,
+    },
+}
+
+#[derive(Clone, Serialize)]
+pub struct TunnelInfo {
+    /// The saved connection the tunnel belongs to.
+    name: String,
+    status: TunnelStatus,
+}
+
+struct Current {
+    info: TunnelInfo,
+    child: Option<Sidecar>,
+}
+
+#[derive(Default)]
+pub struct Tunnels {
+    /// Bumped on every open and close so a supervisor can tell it's stale.
+    generation: u64,
+    current: Option<Current>,
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(Mutex::new(Tunnels::default()));
+}
+
+/// Replace any open tunnel with one for the saved connection `name`.
+pub fn open(app: &AppHandle, name: String, tunnel: SshTunnel, local_port: u16) {
+    let (generation, info) = {
+        let state = app.state::<Mutex<Tunnels>>();
+        let mut tunnels = state.lock_or_recover();
+        tunnels.generation += 1;
+        let info = TunnelInfo {
+            name,
+            status: TunnelStatus::Connecting,
+        };
+        tunnels.current = Some(Current {
+            info: info.clone(),
+            child: None,
+        });
+        (tunnels.generation, info)
+    };
+    let _ = chaos::emit(app, "tunnel-status", info);
+    let app = app.clone();
+    std::thread::spawn(move || supervise(&app, generation, &tunnel, local_port));
+}
+
+/// Close the open tunnel, if any.
+pub fn close(app: &AppHandle) {
+    if let Some(state) = app.try_state::<Mutex<Tunnels>>() {
+        let mut tunnels = state.lock_or_recover();
+        tunnels.generation += 1;
+        tunnels.current = None;
+    }
+}
+
+/// Run `f` on the current tunnel if it's still the one from `generation`.
+/// `None` once it has been closed or replaced.
+fn with_current<T>(
+    app: &AppHandle,
+    generation: u64,
+    f: impl FnOnce(&mut Current) -> T,
+) -> Option<T> {
+    let state = app.state::<Mutex<Tunnels>>();
+    let mut tunnels = state.lock_or_recover();
+    if tunnels.generation != generation {
+        return None;
+    }
+    tunnels.current.as_mut().map(f)
+}
+
+fn set_status(app: &AppHandle, generation: u64, status: TunnelStatus) -> Option<()> {
+    let info = with_current(app, generation, |current| {
+        current.info.status = status;
+        current.info.clone()
+    })?;
+    let _ = chaos::emit(app, "tunnel-status", info);
+    Some(())
+}
+
+/// Keep the tunnel up until it's closed, restarting `ssh` when it exits.
+fn supervise(app: &AppHandle, generation: u64, tunnel: &SshTunnel, local_port: u16) {
+    let mut failures = 0;
+    loop {
+        let last_error = Arc::new(Mutex::new(String::new()));
+        let error_line = last_error.clone();
+        let on_output: sidecar::OutputHandler = Arc::new(move |line, _| {
+            eprintln!("[tunnel] {}", line);
+            *error_line.lock_or_recover() = line.to_string();
+        });
+        let mut command = Command::new("ssh");
+        command.args(tunnel.args(local_port));
+        let error = match Sidecar::spawn(command, local_port, on_output) {
+            Ok(child) => {
+                // If the tunnel was closed meanwhile, the closure and the
+                // child in it are dropped, which kills `ssh`.
+                if with_current(app, generation, |current| current.child = Some(child)).is_none() {
+                    return;
+                }
+                let mut up = false;
+                let status = loop {
+                    std::thread::sleep(POLL);
+                    let exited = with_current(app, generation, |current| {
+                        current.child.as_mut().and_then(Sidecar::exited)
+                    });
+                    match exited {
+                        None => return,
+                        Some(Some(status)) => break status,
+                        Some(None) if !up && sidecar::is_healthy(local_port) => {
+                            up = true;
+                            failures = 0;
+                            eprintln!("[tunnel] Up through {}", tunnel.host);
+                            set_status(app, generation, TunnelStatus::Up);
+                        }
+                        Some(None) => {}
+                    }
+                };
+                let line = last_error.lock_or_recover().clone();
+                if line.is_empty() {
+                    format!("ssh exited ({status})")
+                } else {
+                    line
+                }
+            }
+            Err(e) => e.to_string(),
+        };
+
+        failures += 1;
+        let delay = backoff(failures);
+        eprintln!("[tunnel] {}; retrying in {:?}", error, delay);
+        let retrying = TunnelStatus::Reconnecting {
+            error,
+            retry_in_secs: delay.as_secs(),
+        };
+        if set_status(app, generation, retrying).is_none() {
+            return;
+        }
+        std::thread::sleep(delay);
+        if set_status(app, generation, TunnelStatus::Connecting).is_none() {
+            return;
+        }
+    }
+}
+
+/// The open tunnel's connection and state.
+#[tauri::command]
+pub fn get_tunnel_status(state: State<'_, Mutex<Tunnels>>) -> Option<TunnelInfo> {
+    state
+        .lock_or_recover()
+        .current
+        .as_ref()
+        .map(|current| current.info.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_a_batch_mode_local_forward() {
+        let tunnel = SshTunnel {
+            host: "me@lab".into(),
+            jump: Some("gateway".into()),
+            remote_port: 52836,
+        };
+        assert!(tunnel.validate().is_ok());
+        let args = tunnel.args(9000);
+        assert!(args.contains(&"BatchMode=yes".to_string()));
+        assert!(args.contains(&"127.0.0.1:9000:127.0.0.1:52836".to_string()));
+        assert_eq!(args[args.len() - 4..], ["-J", "gateway", "--", "me@lab"]);
+
+        let injected = SshTunnel {
+            host: "-oProxyCommand=evil".into(),
+            ..tunnel.clone()
+        };
+        assert!(injected.validate().is_err());
+
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(30), MAX_BACKOFF);
+    }
+}
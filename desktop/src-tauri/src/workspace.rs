@@ -0,0 +1,304 @@
+//! Per-session scratch space for intermediate files.
+//!
+//! Each app session gets its own directory under `<app cache>/workspace`,
+//! holding an exclusively locked `.lock` file for as long as the session
+//! runs. The sidecar's `TMPDIR` points into it, and archive extraction
+//! stages there, so everything temporary lives in one place that:
+//!
+//! - is removed on clean exit,
+//! - is removed on the next start if the owning session crashed (its lock
+//!   is no longer held),
+//! - is capped by a disk quota.
+
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::audit::{self, Access};
+use crate::error::{AppError, AppResult, LockExt};
+
+/// Maximum bytes of scratch data per session.
+pub const DEFAULT_QUOTA_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+const LOCK_FILE: &str = ".lock";
+
+pub struct Workspace {
+    session: PathBuf,
+    quota: u64,
+    next_id: AtomicU64,
+    /// Held for the session's lifetime; other sessions treat an unlockable
+    /// directory as live and leave it alone.
+    lock: Mutex<Option<File>>,
+}
+
+#[derive(Serialize)]
+pub struct WorkspaceUsage {
+    path: String,
+    used_bytes: u64,
+    quota_bytes: u64,
+}
+
+/// Total size of all files under `path`.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
+/// Remove session directories under `root` whose owner is no longer running.
+fn remove_stale_sessions(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let live = File::open(dir.join(LOCK_FILE))
+            .map(|lock| lock.try_lock().is_err())
+            .unwrap_or(false);
+        if live {
+            continue;
+        }
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => eprintln!("[workspace] Removed stale scratch dir {}", dir.display()),
+            Err(e) => eprintln!("[workspace] Failed to remove {}: {}", dir.display(), e),
+        }
+    }
+}
+
+impl Workspace {
+    /// Create and lock this session's directory. Once it's locked,
+    /// [`remove_stale_sessions`] can run alongside it.
+    pub fn create(root: &Path, quota: u64) -> io::Result<Self> {
+        fs::create_dir_all(root)?;
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let mut n = 0;
+        let session = loop {
+            let candidate = root.join(format!("{}-{}-{}", stamp, std::process::id(), n));
+            match fs::create_dir(&candidate) {
+                Ok(()) => break candidate,
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => n += 1,
+                Err(e) => return Err(e),
+            }
+        };
+        let lock = File::create(session.join(LOCK_FILE))?;
+        lock.try_lock()
+            .map_err(|e| io::Error::other(format!("Failed to lock scratch dir: {e}")))?;
+        Ok(Self {
+            session,
+            quota,
+            next_id: AtomicU64::new(0),
+            lock: Mutex::new(Some(lock)),
+        })
+    }
+
+    pub fn used(&self) -> u64 {
+        dir_size(&self.session)
+    }
+
+    /// Bytes that can still be written before hitting the quota.
+    pub fn remaining(&self) -> u64 {
+        self.quota.saturating_sub(self.used())
+    }
+
+    /// Create a fresh directory for one piece of work, e.g. `allocate("import")`.
+    pub fn allocate(&self, purpose: &str) -> io::Result<PathBuf> {
+        if self.remaining() == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::StorageFull,
+                "Temporary workspace quota exceeded",
+            ));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let dir = self.session.join(format!("{purpose}-{id}"));
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Delete the allocation containing `path`. Paths outside this session
+    /// are refused.
+    pub fn release(&self, path: &Path) -> io::Result<()> {
+        let path = path.canonicalize()?;
+        let session = self.session.canonicalize()?;
+        let allocation = path
+            .strip_prefix(&session)
+            .ok()
+            .and_then(|rel| rel.components().next())
+            .map(|first| session.join(first))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "Path is not inside the temporary workspace",
+                )
+            })?;
+        fs::remove_dir_all(allocation)
+    }
+
+    /// Directory handed to the sidecar as `TMPDIR`.
+    pub fn backend_tmp(&self) -> io::Result<PathBuf> {
+        let dir = self.session.join("backend");
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    fn remove(&self) {
+        // Close the lock first; Windows refuses to delete open files.
+        self.lock.lock_or_recover().take();
+        if let Err(e) = fs::remove_dir_all(&self.session) {
+            eprintln!(
+                "[workspace] Failed to remove {}: {}",
+                self.session.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Create this session's workspace under the app cache directory.
+pub fn init(app: &AppHandle) {
+    let root = match app.path().app_cache_dir() {
+        Ok(dir) => dir.join("workspace"),
+        Err(e) => {
+            eprintln!("[workspace] Could not resolve app cache directory: {}", e);
+            return;
+        }
+    };
+    match Workspace::create(&root, DEFAULT_QUOTA_BYTES) {
+        Ok(workspace) => {
+            app.manage(workspace);
+            // A crashed import can leave gigabytes behind; don't make the
+            // backend wait on deleting them.
+            std::thread::spawn(move || remove_stale_sessions(&root));
+        }
+        Err(e) => eprintln!("[workspace] Failed to create {}: {}", root.display(), e),
+    }
+}
+
+/// This session's workspace. It's missing when `init` couldn't create it,
+/// and then anything that needs scratch space fails with this error.
+pub fn get(app: &AppHandle) -> AppResult<State<'_, Workspace>> {
+    app.try_state::<Workspace>().ok_or_else(|| {
+        AppError::Unavailable(
+            "No scratch space for this session: the app cache folder couldn't be created".into(),
+        )
+    })
+}
+
+/// Remove this session's scratch directory on clean exit.
+pub fn shutdown(app: &AppHandle) {
+    if let Some(workspace) = app.try_state::<Workspace>() {
+        workspace.remove();
+    }
+}
+
+#[tauri::command]
+pub fn get_workspace_usage(state: State<'_, Workspace>) -> WorkspaceUsage {
+    WorkspaceUsage {
+        path: state.session.to_string_lossy().into_owned(),
+        used_bytes: state.used(),
+        quota_bytes: state.quota,
+    }
+}
+
+/// Free a directory returned by an earlier command (e.g. an extracted archive).
+#[tauri::command]
+pub fn release_workspace_dir(
+    app: AppHandle,
+    state: State<'_, Workspace>,
+    path: String,
+) -> AppResult<()> {
+    state.release(Path::new(&path))?;
+    audit::record(
+        &app,
+        "release_workspace_dir",
+        Access::Delete,
+        Path::new(&path),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "brainshape-workspace-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn allocates_and_releases_inside_session() {
+        let root = temp_root("alloc");
+        let ws = Workspace::create(&root, DEFAULT_QUOTA_BYTES).unwrap();
+        let a = ws.allocate("import").unwrap();
+        let b = ws.allocate("import").unwrap();
+        assert_ne!(a, b);
+        fs::write(a.join("note.md"), "hello").unwrap();
+        assert_eq!(ws.used(), 5);
+
+        ws.release(&a).unwrap();
+        assert!(!a.exists());
+        fs::create_dir_all(b.join("Vault")).unwrap();
+        ws.release(&b.join("Vault")).unwrap();
+        assert!(!b.exists());
+        assert!(ws.release(&root).is_err());
+        ws.remove();
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn enforces_quota() {
+        let root = temp_root("quota");
+        let ws = Workspace::create(&root, 4).unwrap();
+        let dir = ws.allocate("import").unwrap();
+        fs::write(dir.join("big.md"), "12345").unwrap();
+        assert_eq!(ws.remaining(), 0);
+        assert_eq!(
+            ws.allocate("import").unwrap_err().kind(),
+            io::ErrorKind::StorageFull
+        );
+        ws.remove();
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn removes_only_unlocked_sessions() {
+        let root = temp_root("stale");
+        let crashed = root.join("1-1");
+        fs::create_dir_all(&crashed).unwrap();
+        File::create(crashed.join(LOCK_FILE)).unwrap();
+
+        let live = Workspace::create(&root, DEFAULT_QUOTA_BYTES).unwrap();
+        remove_stale_sessions(&root);
+        assert!(!crashed.exists());
+
+        let next = Workspace::create(&root, DEFAULT_QUOTA_BYTES).unwrap();
+        remove_stale_sessions(&root);
+        assert!(live.session.exists());
+        live.remove();
+        next.remove();
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
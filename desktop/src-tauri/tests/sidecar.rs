@@ -0,0 +1,134 @@
+//! Sidecar supervision against the fake server in `tests/support`.
+
+mod support;
+
+use std::time::Duration;
+
+use desktop_lib::sidecar;
+use support::{eventually, spawn, spawn_on, Mode};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[test]
+fn becomes_ready_and_forwards_output() {
+    let (mut sidecar, output) = spawn(Mode::Ready);
+    sidecar.wait_until_ready(TIMEOUT).unwrap();
+    assert!(sidecar::is_healthy(sidecar.port()));
+    assert!(eventually(|| output
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|l| l.starts_with("listening on"))));
+}
+
+#[test]
+fn waits_for_slow_startup() {
+    let (mut sidecar, _) = spawn(Mode::Slow(Duration::from_millis(800)));
+    assert!(!sidecar::is_healthy(sidecar.port()));
+    sidecar.wait_until_ready(TIMEOUT).unwrap();
+
+    let (mut stuck, _) = spawn(Mode::Slow(Duration::from_secs(30)));
+    let err = stuck
+        .wait_until_ready(Duration::from_millis(300))
+        .unwrap_err();
+    assert_eq!(err.code(), "unavailable");
+    assert!(err.to_string().contains("didn't respond"));
+}
+
+#[test]
+fn reports_crash_on_boot() {
+    let (mut sidecar, output) = spawn(Mode::Crash);
+    let err = sidecar.wait_until_ready(TIMEOUT).unwrap_err();
+    assert!(err.to_string().contains("exited during startup"));
+    assert_eq!(sidecar.exited().and_then(|s| s.code()), Some(3));
+    assert!(eventually(|| output
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|l| l.contains("crashing on boot"))));
+}
+
+#[test]
+fn detects_mid_session_exit() {
+    let (mut sidecar, _) = spawn(Mode::ExitAfter(Duration::from_millis(500)));
+    sidecar.wait_until_ready(TIMEOUT).unwrap();
+    assert!(eventually(|| sidecar.exited().is_some()));
+    assert_eq!(sidecar.exited().and_then(|s| s.code()), Some(4));
+    assert!(!sidecar::is_healthy(sidecar.port()));
+}
+
+#[test]
+fn kill_frees_the_port_for_a_restart() {
+    let (mut sidecar, _) = spawn(Mode::Ready);
+    sidecar.wait_until_ready(TIMEOUT).unwrap();
+    let port = sidecar.port();
+    sidecar.kill();
+    assert!(sidecar.exited().is_some());
+    assert!(!sidecar::is_healthy(port));
+
+    let (mut restarted, _) = spawn_on(Mode::Ready, port);
+    restarted.wait_until_ready(TIMEOUT).unwrap();
+}
+
+#[test]
+fn runs_with_its_own_console() {
+    let port = support::free_port();
+    let mut sidecar =
+        sidecar::Sidecar::spawn_in_console(support::command(&Mode::Ready, port), port).unwrap();
+    sidecar.wait_until_ready(TIMEOUT).unwrap();
+}
+
+#[test]
+fn prefers_the_most_specific_build() {
+    let host = |arch, avx2| sidecar::Host {
+        arch,
+        translated: false,
+        avx2,
+    };
+    assert_eq!(
+        host("x86_64", true).variants(),
+        [
+            "brainshape-server-x86_64-avx2",
+            "brainshape-server-x86_64",
+            "brainshape-server"
+        ]
+    );
+    assert_eq!(
+        host("x86_64", false).variants(),
+        ["brainshape-server-x86_64", "brainshape-server"]
+    );
+    assert_eq!(
+        host("aarch64", false).variants(),
+        ["brainshape-server-aarch64", "brainshape-server"]
+    );
+}
+
+#[test]
+fn refuses_a_server_bound_beyond_loopback() {
+    let (mut sidecar, _) = spawn(Mode::Ready);
+    sidecar.wait_until_ready(TIMEOUT).unwrap();
+    sidecar.check_loopback_only().unwrap();
+
+    let (mut exposed, output) = spawn(Mode::Exposed);
+    exposed.wait_until_ready(TIMEOUT).unwrap();
+    // The bind address is parsed on the output thread, which can lag behind
+    // /health; without it the check falls back to probing routable
+    // interfaces, which offline CI doesn't have.
+    assert!(eventually(|| output
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|l| l.starts_with("listening on http://0.0.0.0"))));
+    let err = exposed.check_loopback_only().unwrap_err();
+    assert!(err.to_string().contains("0.0.0.0"));
+
+    assert_eq!(
+        sidecar::reported_bind_host(
+            "INFO:     Uvicorn running on http://127.0.0.1:52836 (Press CTRL+C to quit)"
+        )
+        .as_deref(),
+        Some("127.0.0.1")
+    );
+    assert!(sidecar::is_loopback_host("::1"));
+    assert!(!sidecar::is_loopback_host("0.0.0.0"));
+}
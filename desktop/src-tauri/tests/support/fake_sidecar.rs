@@ -0,0 +1,70 @@
+//! Stand-in for the backend server, compiled by `build.rs` for the tests in
+//! `tests/sidecar.rs`. Takes `--port <PORT>` like the real sidecar and
+//! answers every request with 200. `FAKE_SIDECAR_MODE` picks a scenario:
+//!
+//! - `ready` (default): listen immediately
+//! - `slow`: wait `FAKE_SIDECAR_DELAY_MS` before listening
+//! - `crash`: exit with status 3 without listening
+//! - `exit`: listen, then exit with status 4 after `FAKE_SIDECAR_DELAY_MS`
+//! - `exposed`: listen on every interface instead of loopback
+
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let port = args
+        .iter()
+        .position(|a| a == "--port")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|p| p.parse::<u16>().ok())
+        .expect("--port <PORT> is required");
+    let mode = env::var("FAKE_SIDECAR_MODE").unwrap_or_else(|_| "ready".into());
+    let delay = Duration::from_millis(
+        env::var("FAKE_SIDECAR_DELAY_MS")
+            .ok()
+            .and_then(|d| d.parse().ok())
+            .unwrap_or(0),
+    );
+
+    match mode.as_str() {
+        "crash" => {
+            eprintln!("fake sidecar crashing on boot");
+            process::exit(3);
+        }
+        "slow" => thread::sleep(delay),
+        "exit" => {
+            thread::spawn(move || {
+                thread::sleep(delay);
+                eprintln!("fake sidecar exiting");
+                process::exit(4);
+            });
+        }
+        _ => {}
+    }
+
+    let host = if mode == "exposed" {
+        "0.0.0.0"
+    } else {
+        "127.0.0.1"
+    };
+    let listener = TcpListener::bind((host, port)).expect("port in use");
+    println!("listening on http://{host}:{port}");
+    for mut stream in listener.incoming().map_while(Result::ok) {
+        let mut line = String::new();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+            line.clear();
+        }
+        let body = r#"{"status":"ok"}"#;
+        let _ = write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Helpers for running `desktop_lib::sidecar` against the fake sidecar.
+
+use std::net::TcpListener;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use desktop_lib::sidecar::Sidecar;
+
+pub enum Mode {
+    Ready,
+    Slow(Duration),
+    Crash,
+    ExitAfter(Duration),
+    Exposed,
+}
+
+/// A port nothing is listening on right now.
+pub fn free_port() -> u16 {
+    TcpListener::bind(("127.0.0.1", 0))
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+pub fn command(mode: &Mode, port: u16) -> Command {
+    // Set by build.rs; see its warning if this is missing.
+    let exe = env!("FAKE_SIDECAR");
+    let (name, delay) = match mode {
+        Mode::Ready => ("ready", Duration::ZERO),
+        Mode::Slow(delay) => ("slow", *delay),
+        Mode::Crash => ("crash", Duration::ZERO),
+        Mode::ExitAfter(delay) => ("exit", *delay),
+        Mode::Exposed => ("exposed", Duration::ZERO),
+    };
+    let mut command = Command::new(exe);
+    command
+        .args(["--port", &port.to_string()])
+        .env("FAKE_SIDECAR_MODE", name)
+        .env("FAKE_SIDECAR_DELAY_MS", delay.as_millis().to_string());
+    command
+}
+
+/// Spawn the fake sidecar on `port`, collecting its output lines.
+pub fn spawn_on(mode: Mode, port: u16) -> (Sidecar, Arc<Mutex<Vec<String>>>) {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let lines = output.clone();
+    let sidecar = Sidecar::spawn(
+        command(&mode, port),
+        port,
+        Arc::new(move |line, _| lines.lock().unwrap().push(line.to_string())),
+    )
+    .unwrap();
+    (sidecar, output)
+}
+
+pub fn spawn(mode: Mode) -> (Sidecar, Arc<Mutex<Vec<String>>>) {
+    spawn_on(mode, free_port())
+}
+
+/// Poll `condition` for up to five seconds.
+pub fn eventually(mut condition: impl FnMut() -> bool) -> bool {
+    for _ in 0..50 {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    false
+}
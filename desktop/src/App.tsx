@@ -1,6 +1,13 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import { Group, Panel, Separator, useDefaultLayout, type PanelImperativeHandle } from "react-resizable-panels";
 import { health, getConfig, getNoteFile, getNoteFiles, getSettings, syncStructural, type Config, type HealthStatus, type Settings } from "./lib/api";
+import { restoreUnsavedNotes, startRecoverySnapshots } from "./lib/recovery";
+import { startAutomationReset } from "./lib/automation";
+import { listenBackendSwitched } from "./lib/connections";
+import { acceptOpenedFiles, startDropImport } from "./lib/fileDrop";
+import { listenDeepLinks, takePendingDeepLinks, type DeepLink } from "./lib/deepLink";
+import { listenActions, setActionEnabled } from "./lib/actions";
+import { listenBackendConnection } from "./lib/tauri";
 import { applyTheme, BUILTIN_THEMES, DEFAULT_THEME, THEME_MIGRATION, type Theme } from "./lib/themes";
 import { Sidebar, type SidebarHandle } from "./components/Sidebar";
 import { Editor } from "./components/Editor";
@@ -75,6 +82,8 @@ function App() {
   const [meetingOpen, setMeetingOpen] = useState(false);
   const [shikiTheme, setShikiTheme] = useState<[string, string]>(DEFAULT_THEME.codeTheme);
   const sidebarRef = useRef<SidebarHandle>(null);
+  // Deep links arrive outside the render cycle; always dispatch to the latest handler.
+  const deepLinkRef = useRef<(link: DeepLink) => void>(() => {});
   const sidebarPanelRef = useRef<PanelImperativeHandle>(null);
   const [sidebarOpen, setSidebarOpen] = useState(() => {
     try {
@@ -110,8 +119,35 @@ function App() {
     }
   }, [settings]);
 
+  useEffect(() => {
+    const unlisten = startRecoverySnapshots();
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = startAutomationReset();
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  // The API base URL is resolved once, so start over against the new backend.
+  useEffect(() => {
+    const unlisten = listenBackendSwitched(() => window.location.reload());
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = startDropImport(() => sidebarRef.current?.refresh());
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listenDeepLinks((link) => deepLinkRef.current(link));
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
   useEffect(() => {
     let settingsLoaded = false;
+    let recoveryChecked = false;
     let intervalId: ReturnType<typeof setInterval>;
     async function checkConnection() {
       try {
@@ -127,6 +163,17 @@ function App() {
           settingsLoaded = true;
           setNeedsSetup(!s.notes_path);
         }
+        if (!recoveryChecked && settingsLoaded) {
+          recoveryChecked = true;
+          restoreUnsavedNotes()
+            .then((restored) => { if (restored > 0) sidebarRef.current?.refresh(); })
+            .catch((err) => console.error("Crash recovery failed:", err));
+          // Files opened with the app at launch wait until the backend is up.
+          acceptOpenedFiles().catch((err) => console.error("Opening files failed:", err));
+          takePendingDeepLinks()
+            .then((links) => links.forEach((link) => deepLinkRef.current(link)))
+            .catch((err) => console.error("Deep links failed:", err));
+        }
         // Slow down polling once connected
         clearInterval(intervalId);
         intervalId = setInterval(checkConnection, 10000);
@@ -140,7 +187,16 @@ function App() {
     }
     checkConnection();
     intervalId = setInterval(checkConnection, 2000);
-    return () => clearInterval(intervalId);
+    // The shell reports the backend becoming ready or an external one
+    // restarting; recheck right away rather than on the next poll.
+    const unlisten = listenBackendConnection((up) => {
+      checkConnection();
+      if (up) sidebarRef.current?.refresh();
+    });
+    return () => {
+      clearInterval(intervalId);
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   const handleSelectFile = useCallback(
@@ -182,6 +238,10 @@ function App() {
     [handleSelectFile]
   );
 
+  deepLinkRef.current = (link) => {
+    if (link.action === "open") handleNavigateToNote(link.path);
+  };
+
   const handleNavigateByTitle = useCallback(
     async (title: string) => {
       try {
@@ -243,6 +303,24 @@ function App() {
   const handleSync = useCallback(() => { syncStructural().catch(console.error); }, []);
   const handleOpenSettings = useCallback(() => { setSettingsOpen(true); }, []);
 
+  // Actions from the Rust registry (palette, global shortcuts).
+  useEffect(() => {
+    const unlisten = listenActions((id) => {
+      if (id === "new-note") handleCreateNote();
+      else if (id === "sync") handleSync();
+      else if (id === "open-settings") handleOpenSettings();
+      else if (id.startsWith("view-")) setActiveView(id.slice("view-".length) as ActiveView);
+    });
+    return () => { unlisten.then((fn) => fn()); };
+  }, [handleCreateNote, handleSync, handleOpenSettings]);
+
+  // Backend-dependent actions are unavailable while disconnected.
+  useEffect(() => {
+    for (const id of ["new-note", "sync"]) {
+      setActionEnabled(id, connected).catch(console.error);
+    }
+  }, [connected]);
+
   const handleCloseSettings = useCallback(() => {
     setSettingsDirty(false);
     setSettingsOpen(false);
@@ -411,9 +489,6 @@ function App() {
         onClose={() => setPaletteOpen(false)}
         onSelectNote={handleSelectFile}
         onCreateNote={handleCreateNote}
-        onSwitchView={(v) => setActiveView(v as ActiveView)}
-        onSync={handleSync}
-        onOpenSettings={handleOpenSettings}
       />
     </div>
   );
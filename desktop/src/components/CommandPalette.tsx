@@ -1,11 +1,13 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { getNoteFiles, createNoteFile, syncStructural, type NoteFile } from "../lib/api";
+import { invokeAction, listActions, type ActionInfo } from "../lib/actions";
 import { Input } from "./ui/input";
 
 interface Command {
   id: string;
   label: string;
   category: "note" | "action";
+  shortcut?: string | null;
   action: () => void;
 }
 
@@ -14,9 +16,6 @@ interface CommandPaletteProps {
   onClose: () => void;
   onSelectNote: (path: string) => void;
   onCreateNote: () => void;
-  onSwitchView: (view: string) => void;
-  onSync: () => void;
-  onOpenSettings?: () => void;
 }
 
 export function CommandPalette({
@@ -24,12 +23,10 @@ export function CommandPalette({
   onClose,
   onSelectNote,
   onCreateNote,
-  onSwitchView,
-  onSync,
-  onOpenSettings,
 }: CommandPaletteProps) {
   const [query, setQuery] = useState("");
   const [notes, setNotes] = useState<NoteFile[]>([]);
+  const [actions, setActions] = useState<ActionInfo[]>([]);
   const [selectedIndex, setSelectedIndex] = useState(0);
   const inputRef = useRef<HTMLInputElement>(null);
   const mouseMovedRef = useRef(false);
@@ -43,6 +40,7 @@ export function CommandPalette({
       getNoteFiles()
         .then((res) => setNotes(res.files))
         .catch(console.error);
+      listActions().then(setActions).catch(console.error);
       // Focus after render
       requestAnimationFrame(() => inputRef.current?.focus());
     }
@@ -73,64 +71,25 @@ export function CommandPalette({
         id: "new-note",
         label: query.trim() ? `New Note: "${query.trim()}"` : "New Note",
         category: "action",
+        shortcut: actions.find((a) => a.id === "new-note")?.shortcut,
         action: handleCreateNote,
       },
-      {
-        id: "view-editor",
-        label: "Switch to Editor",
-        category: "action",
-        action: () => {
-          onClose();
-          onSwitchView("editor");
-        },
-      },
-      {
-        id: "view-graph",
-        label: "Switch to Graph",
-        category: "action",
-        action: () => {
-          onClose();
-          onSwitchView("graph");
-        },
-      },
-      {
-        id: "view-memory",
-        label: "Switch to Memory",
-        category: "action",
-        action: () => {
-          onClose();
-          onSwitchView("memory");
-        },
-      },
-      {
-        id: "view-search",
-        label: "Switch to Search",
-        category: "action",
-        action: () => {
-          onClose();
-          onSwitchView("search");
-        },
-      },
-      {
-        id: "view-settings",
-        label: "Open Settings",
-        category: "action",
-        action: () => {
-          onClose();
-          onOpenSettings?.();
-        },
-      },
-      {
-        id: "sync",
-        label: "Run Sync",
-        category: "action",
-        action: () => {
-          onClose();
-          onSync();
-        },
-      },
+      // Everything else comes from the Rust action registry. New Note stays
+      // local so a typed query becomes the title.
+      ...actions
+        .filter((a) => a.enabled && a.id !== "new-note")
+        .map((a) => ({
+          id: a.id,
+          label: a.title,
+          category: "action" as const,
+          shortcut: a.shortcut,
+          action: () => {
+            onClose();
+            invokeAction(a.id).catch(console.error);
+          },
+        })),
     ],
-    [query, handleCreateNote, onClose, onSwitchView, onSync, onOpenSettings]
+    [query, handleCreateNote, actions, onClose]
   );
 
   const noteCommands: Command[] = useMemo(
@@ -222,6 +181,9 @@ export function CommandPalette({
                   {cmd.category === "action" ? ">" : "#"}
                 </span>
                 <span className="truncate">{cmd.label}</span>
+                {cmd.shortcut && (
+                  <span className="ml-auto text-xs text-muted-foreground flex-shrink-0">{cmd.shortcut}</span>
+                )}
               </button>
             ))
           )}
@@ -7,6 +7,7 @@ import { languages } from "@codemirror/language-data";
 import { GFM } from "@lezer/markdown";
 import { vim } from "@replit/codemirror-vim";
 import { updateNoteFile } from "../lib/api";
+import { markSaved, markUnsaved } from "../lib/recovery";
 import { brainshapeAutocompletion, prefetchCompletions } from "../lib/completions";
 import { wikilinkExtension, setWikilinkNavigate } from "../lib/wikilinks";
 import { inlineMarkdownExtension } from "../lib/inlineMarkdown";
@@ -82,9 +83,11 @@ export function Editor({ filePath, content, onNavigateToNote, keymap: keymapMode
     if (!currentPath) return;
     if (saveTimeoutRef.current) clearTimeout(saveTimeoutRef.current);
     setSaveStatus("saving");
+    markUnsaved(currentPath, text);
     saveTimeoutRef.current = setTimeout(async () => {
       try {
         await updateNoteFile(currentPath, text);
+        markSaved(currentPath, text);
         setSaveStatus("saved");
         if (saveStatusTimeoutRef.current) clearTimeout(saveStatusTimeoutRef.current);
         saveStatusTimeoutRef.current = setTimeout(() => setSaveStatus("idle"), 2000);
@@ -17,7 +17,22 @@ import {
   applyTheme,
   type Theme,
 } from "../lib/themes";
-import { isTauri, pickDirectory } from "../lib/tauri";
+import { createBackup, describeError, exportAuditLog, getSandbox, getShortcuts, importArchive, isTauri, pickArchive, pickDirectory, releaseWorkspaceDir, reportProblem, restoreBackup, setShortcut, type Sandbox, type ShortcutAction, type ShortcutStatus } from "../lib/tauri";
+import { listenFeatureFlags, listFeatureFlags, setFeatureFlag, type FeatureFlag } from "../lib/flags";
+import { cancelTask, isCancelled } from "../lib/tasks";
+import {
+  getTunnelStatus,
+  listConnections,
+  listenTunnelStatus,
+  removeConnection,
+  saveConnection,
+  setDefaultConnection,
+  switchConnection,
+  testConnection,
+  type Connections,
+  type TunnelInfo,
+} from "../lib/connections";
+import { choosePythonEnv, clearPythonEnv, getPythonInterpreter } from "../lib/pythonEnv";
 import { Button } from "./ui/button";
 import { Input } from "./ui/input";
 
@@ -100,6 +115,372 @@ function FieldHint({ children }: { children: React.ReactNode }) {
   return <p className="text-xs text-muted-foreground">{children}</p>;
 }
 
+const SHORTCUT_LABELS: Record<ShortcutAction, string> = {
+  toggle_window: "Show / Hide Window",
+  new_note: "New Note",
+  search: "Search",
+};
+
+/** Global shortcuts apply immediately rather than on Save, since the OS owns them. */
+function GlobalShortcuts() {
+  const [shortcuts, setShortcuts] = useState<ShortcutStatus[]>([]);
+  const [drafts, setDrafts] = useState<Partial<Record<ShortcutAction, string>>>({});
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    getShortcuts().then(setShortcuts).catch(console.error);
+  }, []);
+
+  const commit = async (action: ShortcutAction) => {
+    const draft = drafts[action];
+    if (draft === undefined) return;
+    setError("");
+    try {
+      setShortcuts(await setShortcut(action, draft.trim() || null));
+      setDrafts((d) => ({ ...d, [action]: undefined }));
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <SectionHeading>Global Shortcuts</SectionHeading>
+      <FieldHint>
+        Work even when Brainshape isn't focused. Use names like CommandOrControl+Alt+N; leave empty to disable.
+      </FieldHint>
+      {shortcuts.map((s) => (
+        <section key={s.action} className="space-y-1">
+          <div className="flex items-center justify-between gap-2">
+            <FieldLabel>{SHORTCUT_LABELS[s.action]}</FieldLabel>
+            <Input
+              className="h-7 w-56 text-xs"
+              value={drafts[s.action] ?? s.accelerator ?? ""}
+              placeholder="Disabled"
+              onChange={(e) => setDrafts((d) => ({ ...d, [s.action]: e.target.value }))}
+              onBlur={() => commit(s.action)}
+              onKeyDown={(e) => { if (e.key === "Enter") commit(s.action); }}
+            />
+          </div>
+          {s.error && <p className="text-xs text-destructive">Not available: {s.error}</p>}
+        </section>
+      ))}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+/** Experimental features; like shortcuts, changes apply immediately. */
+function FeatureFlags() {
+  const [flags, setFlags] = useState<FeatureFlag[]>([]);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    listFeatureFlags().then(setFlags).catch(console.error);
+    const unlisten = listenFeatureFlags(setFlags);
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  const toggle = async (flag: FeatureFlag) => {
+    setError("");
+    try {
+      setFlags(await setFeatureFlag(flag.id, !flag.enabled));
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const reset = async (flag: FeatureFlag) => {
+    setError("");
+    try {
+      setFlags(await setFeatureFlag(flag.id, null));
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  if (flags.length === 0) return null;
+
+  return (
+    <div className="space-y-4">
+      <SectionHeading>Experimental Features</SectionHeading>
+      {flags.map((flag) => (
+        <section key={flag.id} className="space-y-1">
+          <div className="flex items-center justify-between gap-2">
+            <FieldLabel>{flag.id}</FieldLabel>
+            <div className="flex items-center gap-2">
+              {flag.overridden && (
+                <button
+                  type="button"
+                  onClick={() => reset(flag)}
+                  className="text-xs text-muted-foreground hover:text-foreground"
+                >
+                  Reset
+                </button>
+              )}
+              <button
+                type="button"
+                role="switch"
+                aria-checked={flag.enabled}
+                aria-label={flag.id}
+                onClick={() => toggle(flag)}
+                className={`relative w-9 h-5 rounded-full transition-colors ${flag.enabled ? "bg-primary" : "bg-muted"}`}
+              >
+                <span className={`absolute top-0.5 left-0.5 w-4 h-4 rounded-full bg-white transition-transform ${flag.enabled ? "translate-x-4" : ""}`} />
+              </button>
+            </div>
+          </div>
+          <FieldHint>{flag.description}</FieldHint>
+        </section>
+      ))}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+/** Saved backends; switching reloads the app, so there's nothing to save. */
+function BackendConnections() {
+  const [connections, setConnections] = useState<Connections | null>(null);
+  const [name, setName] = useState("");
+  const [url, setUrl] = useState("");
+  const [sshHost, setSshHost] = useState("");
+  const [remotePort, setRemotePort] = useState("");
+  const [tunnel, setTunnel] = useState<TunnelInfo | null>(null);
+  const [status, setStatus] = useState("");
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    listConnections().then(setConnections).catch(console.error);
+    getTunnelStatus().then(setTunnel).catch(console.error);
+    const unlisten = listenTunnelStatus(setTunnel);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const run = async (action: () => Promise<Connections | void>) => {
+    setStatus("");
+    setError("");
+    try {
+      const updated = await action();
+      if (updated) setConnections(updated);
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const add = () =>
+    run(async () => {
+      const host = sshHost.trim();
+      const updated = await saveConnection(
+        name,
+        url.trim(),
+        host ? { host, jump: null, remote_port: Number(remotePort) || 0 } : null,
+      );
+      setName("");
+      setUrl("");
+      setSshHost("");
+      setRemotePort("");
+      return updated;
+    });
+
+  const test = (target: string) =>
+    run(async () => {
+      setStatus((await testConnection(target)) ? `${target} is answering` : `Nothing answering at ${target}`);
+    });
+
+  if (!connections) return null;
+  const active = connections.active.kind === "external" ? connections.active.name : null;
+  const rows: { name: string | null; label: string; url: string | null; via?: string }[] = [
+    { name: null, label: "Built-in", url: null },
+    ...connections.saved.map((c) => ({
+      name: c.name,
+      label: c.name,
+      url: c.url,
+      via: c.tunnel ? `${c.tunnel.host}:${c.tunnel.remote_port}` : undefined,
+    })),
+  ];
+  const tunnelState = (info: TunnelInfo) =>
+    info.status.state === "reconnecting"
+      ? `Tunnel down: ${info.status.error}. Retrying in ${info.status.retry_in_secs}s`
+      : info.status.state === "up"
+        ? "Tunnel up"
+        : "Tunnel connecting...";
+
+  return (
+    <div className="space-y-4">
+      <SectionHeading>Backend Connections</SectionHeading>
+      <FieldHint>
+        Use a Brainshape server already running on this machine, e.g. on another port. To reach one on another
+        computer, give an SSH host and the server's port there; the URL is then the local end of the tunnel. SSH
+        uses your keys, agent, and SSH config without prompting. Switching reloads the app.
+      </FieldHint>
+      {rows.map((row) => (
+        <section key={row.name ?? ""} className="flex items-center justify-between gap-2">
+          <div className="min-w-0">
+            <FieldLabel>
+              {row.label}
+              {row.name === active && " (connected)"}
+              {row.name === connections.default && " · default"}
+            </FieldLabel>
+            {row.url && (
+              <p className="text-xs text-muted-foreground truncate">
+                {row.url}
+                {row.via && ` via ${row.via}`}
+              </p>
+            )}
+            {tunnel && row.name === tunnel.name && row.name === active && (
+              <p className="text-xs text-muted-foreground">{tunnelState(tunnel)}</p>
+            )}
+          </div>
+          <div className="flex gap-1 shrink-0">
+            {row.url && (
+              <Button variant="ghost" size="sm" className="h-7" onClick={() => test(row.url!)}>
+                Test
+              </Button>
+            )}
+            {row.name !== connections.default && (
+              <Button variant="ghost" size="sm" className="h-7" onClick={() => run(() => setDefaultConnection(row.name))}>
+                Make Default
+              </Button>
+            )}
+            {row.name !== active && (
+              <Button variant="outline" size="sm" className="h-7" onClick={() => run(() => switchConnection(row.name))}>
+                Connect
+              </Button>
+            )}
+            {row.name !== null && (
+              <Button variant="ghost" size="sm" className="h-7" onClick={() => run(() => removeConnection(row.name!))}>
+                Remove
+              </Button>
+            )}
+          </div>
+        </section>
+      ))}
+      <div className="flex gap-2">
+        <Input className="h-8 w-32 text-xs" value={name} placeholder="Name" onChange={(e) => setName(e.target.value)} />
+        <Input
+          className="h-8 flex-1 text-xs"
+          value={url}
+          placeholder="http://127.0.0.1:8765"
+          onChange={(e) => setUrl(e.target.value)}
+        />
+        <Button variant="outline" size="sm" className="h-8" onClick={add} disabled={!name.trim() || !url.trim()}>
+          Save
+        </Button>
+      </div>
+      <div className="flex gap-2">
+        <Input
+          className="h-8 flex-1 text-xs"
+          value={sshHost}
+          placeholder="SSH host (optional), e.g. me@lab"
+          onChange={(e) => setSshHost(e.target.value)}
+        />
+        <Input
+          className="h-8 w-32 text-xs"
+          value={remotePort}
+          placeholder="Remote port"
+          inputMode="numeric"
+          onChange={(e) => setRemotePort(e.target.value)}
+        />
+      </div>
+      {status && <p className="text-sm text-muted-foreground">{status}</p>}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+/** Expert option: run the built-in backend from a Python environment. */
+function PythonEnvironment() {
+  const [current, setCurrent] = useState<string | null>(null);
+  const [status, setStatus] = useState("");
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    getPythonInterpreter().then(setCurrent).catch(console.error);
+  }, []);
+
+  const run = async (action: () => Promise<void>) => {
+    setStatus("");
+    setError("");
+    try {
+      await action();
+      setCurrent(await getPythonInterpreter());
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const choose = () =>
+    run(async () => {
+      const env = await choosePythonEnv();
+      if (env) setStatus(`Using Brainshape ${env.brainshape} on Python ${env.python}`);
+    });
+
+  return (
+    <div className="space-y-4">
+      <SectionHeading>Python Environment</SectionHeading>
+      <FieldHint>
+        For backend development: run the built-in backend with <code>python -m brainshape.server</code> from a
+        virtualenv or conda env with Brainshape installed, e.g. the repository's <code>.venv</code> folder after{" "}
+        <code>uv sync</code>. Restarts the backend.
+      </FieldHint>
+      <div className="flex items-center justify-between gap-2">
+        <FieldLabel>{current ?? "Bundled backend"}</FieldLabel>
+        <div className="flex gap-1 shrink-0">
+          <Button variant="outline" size="sm" className="h-8" onClick={choose}>
+            Choose Environment...
+          </Button>
+          {current && (
+            <Button variant="ghost" size="sm" className="h-8" onClick={() => run(clearPythonEnv)}>
+              Use Bundled
+            </Button>
+          )}
+        </div>
+      </div>
+      {status && <p className="text-sm text-muted-foreground">{status}</p>}
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+/** Files a GitHub issue through the browser; there's no support endpoint. */
+function ReportProblem() {
+  const [message, setMessage] = useState("");
+  const [error, setError] = useState("");
+
+  const submit = async () => {
+    setError("");
+    try {
+      await reportProblem(message);
+      setMessage("");
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <SectionHeading>Report a Problem</SectionHeading>
+      <textarea
+        value={message}
+        onChange={(e) => setMessage(e.target.value)}
+        placeholder="What happened, and what did you expect?"
+        rows={4}
+        className="w-full rounded-md border border-input bg-transparent px-3 py-2 text-sm"
+      />
+      <FieldHint>
+        Opens a GitHub issue in your browser with this message, the app version, and your OS. Review it there before
+        submitting; no logs or notes are attached.
+      </FieldHint>
+      <Button variant="outline" size="sm" onClick={submit} disabled={!message.trim()}>
+        Open Issue...
+      </Button>
+      {error && <p className="text-sm text-destructive">{error}</p>}
+    </div>
+  );
+}
+
 function ModelSelect({
   value,
   suggestions,
@@ -265,9 +646,21 @@ export function SettingsPanel({ dirty, setDirty }: SettingsPanelProps) {
   // Import
   const [importPath, setImportPath] = useState("");
   const [importing, setImporting] = useState(false);
+  const [extractProgress, setExtractProgress] = useState<number | null>(null);
+  const [extractTask, setExtractTask] = useState<number | null>(null);
   const [importResult, setImportResult] = useState<{ files_copied: number; files_skipped: number; folders_created: number } | null>(null);
   const [importError, setImportError] = useState("");
 
+  // Backup
+  const [backupBusy, setBackupBusy] = useState(false);
+  const [backupStatus, setBackupStatus] = useState("");
+  const [backupError, setBackupError] = useState("");
+
+  const [sandbox, setSandbox] = useState<Sandbox>("none");
+  useEffect(() => {
+    getSandbox().then(setSandbox).catch(console.error);
+  }, []);
+
   const fetchSettings = useCallback(async () => {
     try {
       setLoading(true);
@@ -468,6 +861,67 @@ export function SettingsPanel({ dirty, setDirty }: SettingsPanelProps) {
     }
   };
 
+  const runBackupAction = async (action: () => Promise<string | null>) => {
+    setBackupBusy(true);
+    setBackupStatus("");
+    setBackupError("");
+    try {
+      const status = await action();
+      if (status) setBackupStatus(status);
+    } catch (err) {
+      if (!isCancelled(err)) setBackupError(describeError(err));
+    } finally {
+      setBackupBusy(false);
+    }
+  };
+
+  const handleCreateBackup = () =>
+    runBackupAction(async () => {
+      const info = await createBackup();
+      return info && `Backup saved (${info.files} files)`;
+    });
+
+  const handleExportAuditLog = () =>
+    runBackupAction(async () => {
+      const bytes = await exportAuditLog();
+      return bytes !== null ? "Access log exported" : null;
+    });
+
+  const handleRestoreBackup = () =>
+    runBackupAction(async () => {
+      const files = await restoreBackup();
+      return files !== null ? `Restore staged (${files} files). Restart Brainshape to apply it.` : null;
+    });
+
+  const handleImportArchive = async () => {
+    const archive = await pickArchive("Select Archive to Import");
+    if (!archive) return;
+    setImporting(true);
+    setImportResult(null);
+    setImportError("");
+    setExtractProgress(0);
+    try {
+      const extracted = await importArchive(archive, (fraction, taskId) => {
+        setExtractProgress(fraction);
+        setExtractTask(taskId);
+      });
+      setExtractProgress(null);
+      setExtractTask(null);
+      try {
+        const result = await importVault(extracted.path);
+        setImportResult(result.stats);
+      } finally {
+        releaseWorkspaceDir(extracted.path).catch((err) => console.error("Cleanup failed:", err));
+      }
+    } catch (err) {
+      if (!isCancelled(err)) setImportError(describeError(err));
+    } finally {
+      setExtractProgress(null);
+      setExtractTask(null);
+      setImporting(false);
+    }
+  };
+
   if (loading) {
     return (
       <div className="flex-1 flex items-center justify-center text-muted-foreground text-sm">
@@ -517,6 +971,12 @@ export function SettingsPanel({ dirty, setDirty }: SettingsPanelProps) {
                 )}
               </div>
               <FieldHint>Directory where your markdown notes are stored.</FieldHint>
+              {sandbox !== "none" && (
+                <FieldHint>
+                  Brainshape is running in a {sandbox === "flatpak" ? "Flatpak" : "Snap"} sandbox.
+                  Choose folders with Browse so the sandbox grants access; typed paths may not be reachable.
+                </FieldHint>
+              )}
             </section>
           </div>
 
@@ -732,12 +1192,22 @@ export function SettingsPanel({ dirty, setDirty }: SettingsPanelProps) {
             </section>
           </div>
 
+          {isTauri() && <GlobalShortcuts />}
+
+          {isTauri() && <FeatureFlags />}
+
+          {isTauri() && <BackendConnections />}
+
+          {isTauri() && <PythonEnvironment />}
+
+          {isTauri() && <ReportProblem />}
+
           {/* ── Import Notes ── */}
           <div className="space-y-4">
             <SectionHeading>Import Notes</SectionHeading>
             <FieldHint>
-              Copy markdown notes from another directory into your Brainshape notes folder.
-              Preserves folder structure. Only .md files are imported.
+              Copy markdown notes from another directory (or a .zip / .tar.gz export) into your
+              Brainshape notes folder. Preserves folder structure. Only .md files are imported.
             </FieldHint>
 
             <section className="space-y-1.5">
@@ -771,6 +1241,29 @@ export function SettingsPanel({ dirty, setDirty }: SettingsPanelProps) {
                 >
                   {importing ? "Importing..." : "Import"}
                 </Button>
+                {isTauri() && (
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    className="h-8"
+                    onClick={handleImportArchive}
+                    disabled={importing}
+                  >
+                    {extractProgress !== null
+                      ? `Extracting ${Math.round(extractProgress * 100)}%`
+                      : "Archive..."}
+                  </Button>
+                )}
+                {extractTask !== null && (
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    className="h-8"
+                    onClick={() => cancelTask(extractTask).catch(console.error)}
+                  >
+                    Cancel
+                  </Button>
+                )}
               </div>
             </section>
 
@@ -788,6 +1281,41 @@ export function SettingsPanel({ dirty, setDirty }: SettingsPanelProps) {
             )}
           </div>
 
+          {/* ── Backup ── */}
+          {isTauri() && (
+            <div className="space-y-4">
+              <SectionHeading>Backup</SectionHeading>
+              <FieldHint>
+                Back up settings (including API keys), the knowledge graph, and agent memories to a
+                .zip file. Notes are not included — they are plain files in your notes folder. The backend
+                restarts while a backup is made, so the database is copied at rest.
+              </FieldHint>
+
+              <div className="flex gap-2">
+                <Button variant="outline" size="sm" className="h-8" onClick={handleCreateBackup} disabled={backupBusy}>
+                  Create Backup...
+                </Button>
+                <Button variant="outline" size="sm" className="h-8" onClick={handleRestoreBackup} disabled={backupBusy}>
+                  Restore...
+                </Button>
+                <Button variant="outline" size="sm" className="h-8" onClick={handleExportAuditLog} disabled={backupBusy}>
+                  Export Access Log...
+                </Button>
+              </div>
+              <FieldHint>
+                The access log records every file Brainshape itself reads, writes, opens, or deletes
+                (imports, backups, dropped files), with the time and user.
+              </FieldHint>
+
+              {backupStatus && (
+                <p className="text-sm text-muted-foreground">{backupStatus}</p>
+              )}
+              {backupError && (
+                <p className="text-sm text-destructive">{backupError}</p>
+              )}
+            </div>
+          )}
+
           {/* ── Language Model ── */}
           <div className="space-y-4">
             <SectionHeading>Language Model</SectionHeading>
@@ -1,5 +1,6 @@
 import { forwardRef, useCallback, useEffect, useImperativeHandle, useRef, useState } from "react";
 import { createFolder, createNoteFile, deleteFolder, deleteNoteFile, emptyTrash, getNoteFiles, getTrashNotes, moveNoteFile, renameFolder, renameNoteFile, restoreFromTrash, syncStructural, type NoteFile } from "../lib/api";
+import { fileManagerName, isTauri, openNoteExternally, revealNote } from "../lib/tauri";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Input } from "@/components/ui/input";
 
@@ -412,6 +413,11 @@ export const Sidebar = forwardRef<SidebarHandle, SidebarProps>(function Sidebar(
   };
 
   // File rename handlers
+  const handleExternalClick = (action: (path: string) => Promise<void>, path: string) => {
+    setMenu(null);
+    action(path).catch((err) => console.error("Failed to open externally:", err));
+  };
+
   const handleRenameClick = (path: string) => {
     setMenu(null);
     const file = files.find((f) => f.path === path);
@@ -805,7 +811,7 @@ export const Sidebar = forwardRef<SidebarHandle, SidebarProps>(function Sidebar(
             className="fixed z-50 min-w-[140px] rounded-md border border-border bg-popover p-1 shadow-md"
             style={{
               left: Math.min(menu.x, window.innerWidth - 160),
-              top: Math.min(menu.y, window.innerHeight - 120),
+              top: Math.min(menu.y, window.innerHeight - 200),
             }}
           >
             {menu.type === "file" && (
@@ -822,6 +828,23 @@ export const Sidebar = forwardRef<SidebarHandle, SidebarProps>(function Sidebar(
                 >
                   Move to Trash
                 </button>
+                {isTauri() && (
+                  <>
+                    <div className="my-1 border-t border-border" />
+                    <button
+                      onClick={() => handleExternalClick(openNoteExternally, menu.path)}
+                      className={menuBtnClass}
+                    >
+                      Open in Default App
+                    </button>
+                    <button
+                      onClick={() => handleExternalClick(revealNote, menu.path)}
+                      className={menuBtnClass}
+                    >
+                      Reveal in {fileManagerName()}
+                    </button>
+                  </>
+                )}
               </>
             )}
             {menu.type === "folder" && (
@@ -851,6 +874,14 @@ export const Sidebar = forwardRef<SidebarHandle, SidebarProps>(function Sidebar(
                 >
                   Delete
                 </button>
+                {isTauri() && (
+                  <button
+                    onClick={() => handleExternalClick(revealNote, menu.path)}
+                    className={menuBtnClass}
+                  >
+                    Reveal in {fileManagerName()}
+                  </button>
+                )}
               </>
             )}
             {menu.type === "background" && (
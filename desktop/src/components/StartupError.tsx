@@ -0,0 +1,64 @@
+import { useEffect, useState } from "react";
+import { Button } from "@/components/ui/button";
+import {
+  getStartupError,
+  openBackendLogs,
+  retryBackend,
+  runWithoutBackend,
+  type StartupError as StartupErrorInfo,
+} from "../lib/startup";
+import { describeError } from "../lib/tauri";
+
+/** Contents of the window shown when the backend fails to start. */
+export function StartupError() {
+  const [error, setError] = useState<StartupErrorInfo | null>(null);
+  const [actionError, setActionError] = useState("");
+  const [retrying, setRetrying] = useState(false);
+
+  useEffect(() => {
+    getStartupError().then(setError).catch(console.error);
+  }, []);
+
+  const run = async (action: () => Promise<void>) => {
+    setActionError("");
+    try {
+      await action();
+    } catch (err) {
+      setActionError(describeError(err));
+    }
+  };
+
+  const handleRetry = async () => {
+    setRetrying(true);
+    await run(retryBackend);
+    // A failed retry updates the stored error.
+    getStartupError().then(setError).catch(console.error);
+    setRetrying(false);
+  };
+
+  return (
+    <div className="h-screen flex items-center justify-center bg-background text-foreground">
+      <div className="space-y-4 max-w-md px-6">
+        <h1 className="text-lg font-semibold text-destructive">Brainshape couldn't start its backend</h1>
+        {error && (
+          <div className="space-y-2 text-sm">
+            <p className="break-all">{error.message}</p>
+            {error.hint && <p className="text-muted-foreground">{error.hint}</p>}
+          </div>
+        )}
+        {actionError && <p className="text-xs text-destructive">{actionError}</p>}
+        <div className="flex gap-2 justify-end">
+          <Button variant="ghost" onClick={() => run(openBackendLogs)}>
+            Open Logs
+          </Button>
+          <Button variant="outline" onClick={() => run(runWithoutBackend)}>
+            Run Without Backend
+          </Button>
+          <Button onClick={handleRetry} disabled={retrying}>
+            {retrying ? "Retrying..." : "Retry"}
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+}
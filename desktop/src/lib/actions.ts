@@ -0,0 +1,37 @@
+/**
+ * Frontend side of the Rust action registry.
+ *
+ * Action ids and titles live in `actions.rs`, so the command palette and
+ * global shortcuts always agree. Actions the frontend implements arrive as
+ * `action-invoked` events, from whichever surface triggered them.
+ */
+
+import { invokeCommand, isTauri } from "./tauri";
+
+export interface ActionInfo {
+  id: string;
+  title: string;
+  enabled: boolean;
+  shortcut: string | null;
+}
+
+export async function listActions(): Promise<ActionInfo[]> {
+  if (!isTauri()) return [];
+  return invokeCommand<ActionInfo[]>("list_actions");
+}
+
+export async function invokeAction(id: string): Promise<void> {
+  await invokeCommand("invoke_action", { id });
+}
+
+export async function setActionEnabled(id: string, enabled: boolean): Promise<void> {
+  if (!isTauri()) return;
+  await invokeCommand("set_action_enabled", { id, enabled });
+}
+
+/** Handle actions invoked from any surface. Returns an unlisten function. */
+export async function listenActions(onAction: (id: string) => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<string>("action-invoked", (event) => onAction(event.payload));
+}
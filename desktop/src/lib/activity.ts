@@ -0,0 +1,18 @@
+/**
+ * Long jobs the OS shouldn't throttle. On macOS the Rust shell holds an
+ * App Nap assertion between `begin_activity` and `end_activity`, so a sync
+ * or import keeps full speed while the window is hidden.
+ */
+
+import { invokeCommand, isTauri } from "./tauri";
+
+/** Run `job`, keeping the app awake (but not preventing idle sleep) until it settles. */
+export async function withActivity<T>(reason: string, job: () => Promise<T>): Promise<T> {
+  if (!isTauri()) return job();
+  const id = await invokeCommand<number>("begin_activity", { reason }).catch(() => null);
+  try {
+    return await job();
+  } finally {
+    if (id !== null) invokeCommand("end_activity", { id }).catch(console.error);
+  }
+}
@@ -1,22 +1,24 @@
+import { withActivity } from "./activity";
+import { invokeCommand, isTauri } from "./tauri";
+
 /** Resolve the backend base URL.
  *
- * In dev mode (Vite dev server), uses the hardcoded default port.
- * In production (Tauri app), queries the Rust shell for the dynamically
- * assigned port via the `get_backend_port` command.
+ * In a plain browser (Vite dev server without Tauri), uses the default port.
+ * In the Tauri app, queries the Rust shell via the `get_backend_port`
+ * command, which honours `--backend-url`.
  */
 let _baseUrl: string | null = null;
 
 async function resolveBaseUrl(): Promise<string> {
   if (_baseUrl) return _baseUrl;
 
-  if (import.meta.env.DEV) {
+  if (!isTauri()) {
     _baseUrl = "http://127.0.0.1:52836";
     return _baseUrl;
   }
 
   try {
-    const { invoke } = await import("@tauri-apps/api/core");
-    const port = await invoke<number>("get_backend_port");
+    const port = await invokeCommand<number>("get_backend_port");
     _baseUrl = `http://127.0.0.1:${port}`;
   } catch {
     _baseUrl = "http://127.0.0.1:52836";
@@ -186,7 +188,7 @@ export function deleteFolder(
 }
 
 export function syncStructural(): Promise<{ status: string; stats: Record<string, number> }> {
-  return request("/sync/structural", { method: "POST" });
+  return withActivity("Syncing notes", () => request("/sync/structural", { method: "POST" }));
 }
 
 // --- Import ---
@@ -201,10 +203,12 @@ export interface ImportVaultResult {
 }
 
 export function importVault(sourcePath: string): Promise<ImportVaultResult> {
-  return request("/import/vault", {
-    method: "POST",
-    body: JSON.stringify({ source_path: sourcePath }),
-  });
+  return withActivity("Importing notes", () =>
+    request("/import/vault", {
+      method: "POST",
+      body: JSON.stringify({ source_path: sourcePath }),
+    })
+  );
 }
 
 // --- Settings ---
@@ -313,10 +317,12 @@ export async function transcribeMeeting(
   if (folder) formData.append("folder", folder);
   if (tags) formData.append("tags", tags);
   const base = await baseUrlPromise;
-  const res = await fetch(`${base}/transcribe/meeting`, {
-    method: "POST",
-    body: formData,
-  });
+  const res = await withActivity("Transcribing meeting", () =>
+    fetch(`${base}/transcribe/meeting`, {
+      method: "POST",
+      body: formData,
+    })
+  );
   if (!res.ok) {
     const detail = await res.text();
     throw new Error(`${res.status}: ${detail}`);
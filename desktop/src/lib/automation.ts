@@ -0,0 +1,17 @@
+/**
+ * End-to-end test hooks (the `automation` feature flag). A driver resets the
+ * shell with the `automation_reset_state` command; the frontend follows by
+ * dropping its own local state and reloading.
+ */
+
+import { isTauri } from "./tauri";
+
+/** Reload with fresh local state after an automation reset. Returns an unlisten function. */
+export async function startAutomationReset(): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen("automation-reset", () => {
+    localStorage.clear();
+    window.location.reload();
+  });
+}
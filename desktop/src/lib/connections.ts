@@ -0,0 +1,92 @@
+/**
+ * Saved backend connections. A connection is a named backend URL on this
+ * machine, optionally forwarded over SSH to a backend on another one;
+ * switching reloads the app against the new backend.
+ */
+
+import { invokeCommand, isTauri } from "./tauri";
+
+/** An SSH local forward from the connection URL's port to `remote_port`. */
+export interface SshTunnel {
+  /** `user@host` or a Host alias from the SSH config. */
+  host: string;
+  jump?: string | null;
+  remote_port: number;
+}
+
+export interface SavedConnection {
+  name: string;
+  url: string;
+  tunnel?: SshTunnel | null;
+}
+
+export type TunnelStatus =
+  | { state: "connecting" }
+  | { state: "up" }
+  | { state: "reconnecting"; error: string; retry_in_secs: number };
+
+export interface TunnelInfo {
+  /** The saved connection the tunnel belongs to. */
+  name: string;
+  status: TunnelStatus;
+}
+
+export type ActiveConnection =
+  | { kind: "sidecar"; port: number }
+  | { kind: "external"; name: string | null; port: number };
+
+export interface Connections {
+  saved: SavedConnection[];
+  /** Used at launch; null for the built-in backend. */
+  default: string | null;
+  active: ActiveConnection;
+}
+
+export async function listConnections(): Promise<Connections | null> {
+  if (!isTauri()) return null;
+  return invokeCommand<Connections>("list_connections");
+}
+
+/** Save a connection, replacing one with the same name. */
+export async function saveConnection(name: string, url: string, tunnel: SshTunnel | null = null): Promise<Connections> {
+  return invokeCommand<Connections>("save_connection", { name, url, tunnel });
+}
+
+export async function removeConnection(name: string): Promise<Connections> {
+  return invokeCommand<Connections>("remove_connection", { name });
+}
+
+/** Whether a backend answers at `url`. */
+export async function testConnection(url: string): Promise<boolean> {
+  return invokeCommand<boolean>("test_connection", { url });
+}
+
+/** Connect to `name` at launch, or to the built-in backend with null. */
+export async function setDefaultConnection(name: string | null): Promise<Connections> {
+  return invokeCommand<Connections>("set_default_connection", { name });
+}
+
+/** Switch to `name`, or back to the built-in backend with null. */
+export async function switchConnection(name: string | null): Promise<void> {
+  await invokeCommand("switch_connection", { name });
+}
+
+/** Called after the shell switches backends. Returns an unlisten function. */
+export async function listenBackendSwitched(handler: () => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen("backend-switched", () => handler());
+}
+
+/** The open SSH tunnel, or null when the connection doesn't use one. */
+export async function getTunnelStatus(): Promise<TunnelInfo | null> {
+  if (!isTauri()) return null;
+  return invokeCommand<TunnelInfo | null>("get_tunnel_status");
+}
+
+/** Called whenever the open tunnel's state changes. Returns an unlisten function. */
+export async function listenTunnelStatus(handler: (info: TunnelInfo) => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<TunnelInfo>("tunnel-status", (event) => handler(event.payload));
+}
@@ -0,0 +1,24 @@
+/**
+ * `brainshape://` link handling.
+ *
+ * The Rust shell parses and validates links; this module only dispatches
+ * them. Links that launched the app are held in Rust until
+ * `takePendingDeepLinks` is called, once the backend can serve notes.
+ */
+
+import { invokeCommand, isTauri } from "./tauri";
+
+export type DeepLink = { action: "open"; path: string };
+
+/** Listen for links opened while the app is running. Returns an unlisten function. */
+export async function listenDeepLinks(onLink: (link: DeepLink) => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<DeepLink>("deep-link", (event) => onLink(event.payload));
+}
+
+/** Links received during startup. Later links arrive through `listenDeepLinks`. */
+export async function takePendingDeepLinks(): Promise<DeepLink[]> {
+  if (!isTauri()) return [];
+  return invokeCommand<DeepLink[]>("take_pending_deep_links");
+}
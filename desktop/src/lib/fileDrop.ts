@@ -0,0 +1,104 @@
+/**
+ * Import of files dropped onto the window or opened with the app.
+ *
+ * The Rust shell validates the paths and emits `files-dropped` with a
+ * classification for each one, plus a staging folder holding copies of loose
+ * notes. Everything accepted goes through the backend's vault import, so the
+ * usual rules (only `.md`, skip existing) still apply.
+ */
+
+import { importVault } from "./api";
+import { importArchive, invokeCommand, isTauri, releaseWorkspaceDir } from "./tauri";
+
+type DropKind =
+  | "note"
+  | "folder"
+  | "archive"
+  | "empty"
+  | "too_large"
+  | "unsupported"
+  | "missing"
+  | "duplicate";
+
+interface DroppedItem {
+  path: string;
+  kind: DropKind;
+  bytes: number;
+  markdown_files: number;
+}
+
+interface FilesDropped {
+  items: DroppedItem[];
+  notes_dir: string | null;
+}
+
+const REJECTED: Partial<Record<DropKind, string>> = {
+  empty: "no markdown files",
+  too_large: "too large",
+  unsupported: "not a note, folder, or archive",
+  missing: "not found",
+  duplicate: "same name as another dropped note",
+};
+
+async function importDropped({ items, notes_dir }: FilesDropped): Promise<number> {
+  let copied = 0;
+  const failures: string[] = [];
+  for (const item of items) {
+    const reason = REJECTED[item.kind];
+    if (reason) failures.push(`${item.path}: ${reason}`);
+  }
+
+  const importDir = async (dir: string, label: string, release: boolean) => {
+    try {
+      copied += (await importVault(dir)).stats.files_copied;
+    } catch (err) {
+      failures.push(`${label}: ${err instanceof Error ? err.message : String(err)}`);
+    } finally {
+      if (release) releaseWorkspaceDir(dir).catch((err) => console.error("Cleanup failed:", err));
+    }
+  };
+
+  if (notes_dir) await importDir(notes_dir, "Dropped notes", true);
+  for (const item of items) {
+    if (item.kind === "folder") {
+      await importDir(item.path, item.path, false);
+    } else if (item.kind === "archive") {
+      try {
+        const extracted = await importArchive(item.path);
+        await importDir(extracted.path, item.path, true);
+      } catch (err) {
+        failures.push(`${item.path}: ${err instanceof Error ? err.message : String(err)}`);
+      }
+    }
+  }
+
+  await invokeCommand("push_notification", {
+    level: failures.length > 0 ? "warning" : "info",
+    title: `Imported ${copied} note(s)`,
+    body: failures.length > 0 ? `Skipped:\n${failures.join("\n")}` : null,
+  });
+  return copied;
+}
+
+/**
+ * Import whatever is dropped onto the window. `onImported` runs after each
+ * drop that added notes. Returns an unlisten function.
+ */
+export async function startDropImport(onImported: () => void): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<FilesDropped>("files-dropped", (event) => {
+    importDropped(event.payload)
+      .then((copied) => { if (copied > 0) onImported(); })
+      .catch((err) => console.error("Drop import failed:", err));
+  });
+}
+
+/**
+ * Tell the Rust shell the import flow is ready, so files opened with the app
+ * during startup are delivered as `files-dropped`.
+ */
+export async function acceptOpenedFiles(): Promise<void> {
+  if (!isTauri()) return;
+  await invokeCommand("ready_for_opened_files");
+}
@@ -0,0 +1,39 @@
+/**
+ * Runtime feature flags from the Rust shell. Defaults are compiled in;
+ * per-user overrides are stored in the desktop settings.
+ */
+
+import { invokeCommand, isTauri } from "./tauri";
+
+export interface FeatureFlag {
+  id: string;
+  description: string;
+  enabled: boolean;
+  /** The user's setting differs from the default. */
+  overridden: boolean;
+}
+
+export async function listFeatureFlags(): Promise<FeatureFlag[]> {
+  if (!isTauri()) return [];
+  return invokeCommand<FeatureFlag[]>("list_feature_flags");
+}
+
+/** Whether a flag is on. Outside Tauri every flag is off. */
+export async function isFeatureEnabled(id: string): Promise<boolean> {
+  if (!isTauri()) return false;
+  return invokeCommand<boolean>("is_feature_enabled", { id });
+}
+
+/** Override a flag, or pass null to return to its default. */
+export async function setFeatureFlag(id: string, enabled: boolean | null): Promise<FeatureFlag[]> {
+  return invokeCommand<FeatureFlag[]>("set_feature_flag", { id, enabled });
+}
+
+/** Called with the full list whenever a flag changes. Returns an unlisten function. */
+export async function listenFeatureFlags(
+  handler: (flags: FeatureFlag[]) => void
+): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<FeatureFlag[]>("feature-flags-changed", (event) => handler(event.payload));
+}
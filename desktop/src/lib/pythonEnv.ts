@@ -0,0 +1,36 @@
+/**
+ * Running the built-in backend from an existing Python environment instead
+ * of the bundled sidecar, for backend development. The shell picks the
+ * environment in its own dialog; the webview never passes an interpreter.
+ */
+
+import { invokeCommand, isTauri } from "./tauri";
+
+export interface PythonEnv {
+  /** e.g. "3.13.1". */
+  python: string;
+  /** Installed Brainshape version; null when it isn't installed. */
+  brainshape: string | null;
+  /** Required distributions that aren't installed. */
+  missing: string[];
+}
+
+/** The interpreter the built-in backend runs with; null for the bundled one. */
+export async function getPythonInterpreter(): Promise<string | null> {
+  if (!isTauri()) return null;
+  return invokeCommand<string | null>("get_python_interpreter");
+}
+
+/**
+ * Pick a virtualenv or conda env folder and run the built-in backend from
+ * it. Null if cancelled. The built-in backend restarts if it's in use,
+ * which reloads the app.
+ */
+export async function choosePythonEnv(): Promise<PythonEnv | null> {
+  return invokeCommand<PythonEnv | null>("choose_python_env");
+}
+
+/** Go back to the bundled backend. */
+export async function clearPythonEnv(): Promise<void> {
+  await invokeCommand("clear_python_env");
+}
@@ -0,0 +1,87 @@
+/**
+ * Crash recovery for unsaved editor content.
+ *
+ * The editor records content here until the server confirms the save.
+ * The Rust shell periodically asks for a snapshot (`recovery-snapshot-requested`)
+ * and keeps it on disk until the app exits cleanly, so a crash or an
+ * unreachable backend loses at most one snapshot interval of typing.
+ */
+
+import { updateNoteFile } from "./api";
+import { invokeCommand, isTauri } from "./tauri";
+
+interface RecoverySnapshot {
+  unsaved: Record<string, string>;
+}
+
+interface SnapshotInfo {
+  id: string;
+  saved_at: number;
+  bytes: number;
+}
+
+const unsaved = new Map<string, string>();
+
+/** Record content that hasn't reached the server yet. */
+export function markUnsaved(path: string, content: string) {
+  unsaved.set(path, content);
+}
+
+/** Clear a pending entry once the server has saved exactly this content. */
+export function markSaved(path: string, content: string) {
+  if (unsaved.get(path) === content) unsaved.delete(path);
+}
+
+/** Answer snapshot requests from the Rust shell. Returns an unlisten function. */
+export async function startRecoverySnapshots(): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen("recovery-snapshot-requested", () => {
+    const snapshot: RecoverySnapshot = { unsaved: Object.fromEntries(unsaved) };
+    invokeCommand("save_recovery_snapshot", { snapshot }).catch((err) =>
+      console.error("Recovery snapshot failed:", err)
+    );
+  });
+}
+
+/**
+ * Offer to restore notes left unsaved by a crashed session.
+ * Returns the number of notes written back to the server.
+ */
+export async function restoreUnsavedNotes(): Promise<number> {
+  if (!isTauri()) return 0;
+  const snapshots = await invokeCommand<SnapshotInfo[]>("get_recovery_snapshots");
+  if (snapshots.length === 0) return 0;
+
+  // Newest snapshot wins when several sessions left content for the same note.
+  const notes = new Map<string, string>();
+  for (const { id } of [...snapshots].reverse()) {
+    const snapshot = await invokeCommand<RecoverySnapshot>("restore_snapshot", { id });
+    for (const [path, content] of Object.entries(snapshot.unsaved ?? {})) {
+      notes.set(path, content);
+    }
+  }
+
+  const { ask } = await import("@tauri-apps/plugin-dialog");
+  const restore = notes.size > 0 && await ask(
+    `Brainshape didn't shut down cleanly. Restore unsaved changes to ${notes.size} note(s)?\n\n${[...notes.keys()].join("\n")}`,
+    { title: "Restore Unsaved Changes", kind: "warning" }
+  );
+
+  let restored = 0;
+  if (restore) {
+    for (const [path, content] of notes) {
+      try {
+        await updateNoteFile(path, content);
+        restored++;
+      } catch (err) {
+        console.error(`Failed to restore ${path}:`, err);
+        return restored;
+      }
+    }
+  }
+  for (const { id } of snapshots) {
+    await invokeCommand("discard_recovery_snapshot", { id });
+  }
+  return restored;
+}
@@ -0,0 +1,35 @@
+/**
+ * Backend startup failures.
+ *
+ * When the sidecar can't be started, the Rust shell opens a separate
+ * `startup-error` window (this bundle with `?view=startup-error`) instead
+ * of the main window.
+ */
+
+import { invokeCommand } from "./tauri";
+
+export interface StartupError {
+  message: string;
+  hint: string | null;
+}
+
+export function isStartupErrorView(): boolean {
+  return new URLSearchParams(window.location.search).get("view") === "startup-error";
+}
+
+export async function getStartupError(): Promise<StartupError | null> {
+  return invokeCommand<StartupError | null>("get_startup_error");
+}
+
+/** Spawn the backend again. Resolves once it started and the main window is shown. */
+export async function retryBackend(): Promise<void> {
+  await invokeCommand("retry_backend");
+}
+
+export async function runWithoutBackend(): Promise<void> {
+  await invokeCommand("run_without_backend");
+}
+
+export async function openBackendLogs(): Promise<void> {
+  await invokeCommand("open_backend_logs");
+}
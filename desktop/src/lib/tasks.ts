@@ -0,0 +1,41 @@
+/**
+ * Long-running work in the Rust shell (archive extraction, backups, dropped
+ * files). Each command resolves when its task ends; these report progress
+ * meanwhile and let the user cancel.
+ */
+
+import { CommandError, invokeCommand, isTauri } from "./tauri";
+
+export interface TaskInfo {
+  id: number;
+  kind: "import_archive" | "create_backup" | "restore_backup" | "stage_drop";
+  label: string;
+  done: number;
+  /** 0 while the amount of work isn't known. */
+  total: number;
+  cancelling: boolean;
+}
+
+export async function listTasks(): Promise<TaskInfo[]> {
+  if (!isTauri()) return [];
+  return invokeCommand<TaskInfo[]>("list_tasks");
+}
+
+/** Ask a task to stop; its command then rejects with code "cancelled". */
+export async function cancelTask(id: number): Promise<void> {
+  await invokeCommand("cancel_task", { id });
+}
+
+/** Whether a command failed because its task was cancelled. */
+export function isCancelled(err: unknown): boolean {
+  return err instanceof CommandError && err.code === "cancelled";
+}
+
+/** Called with the full list whenever a task starts, progresses, or ends. Returns an unlisten function. */
+export async function listenTasks(
+  handler: (tasks: TaskInfo[]) => void
+): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<TaskInfo[]>("tasks-changed", (event) => handler(event.payload));
+}
@@ -5,9 +5,62 @@
  * actually running inside Tauri (not in browser / Vite dev).
  */
 
-/** Detect if running inside Tauri (vs. plain browser / Vite dev). */
+import type { TaskInfo } from "./tasks";
+
+/**
+ * Detect if running inside Tauri (vs. plain browser / Vite dev). Tauri 2
+ * always injects `__TAURI_INTERNALS__`; `__TAURI__` only exists with
+ * `app.withGlobalTauri`, which this app doesn't set.
+ */
 export function isTauri(): boolean {
-  return "__TAURI__" in window;
+  return "__TAURI_INTERNALS__" in window;
+}
+
+/**
+ * Error returned by a Rust command: `message` is user-facing, `hint`
+ * suggests what to try next, and `code` is stable for branching.
+ */
+export class CommandError extends Error {
+  code: string;
+  hint: string | null;
+
+  constructor(code: string, message: string, hint: string | null = null) {
+    super(message);
+    this.name = "CommandError";
+    this.code = code;
+    this.hint = hint;
+  }
+}
+
+function toCommandError(err: unknown): Error {
+  if (err && typeof err === "object" && "code" in err && "message" in err) {
+    const { code, message, hint } = err as {
+      code: string;
+      message: string;
+      hint?: string | null;
+    };
+    return new CommandError(code, message, hint ?? null);
+  }
+  return err instanceof Error ? err : new CommandError("internal", String(err));
+}
+
+/** Message for display, followed by the command's hint when it has one. */
+export function describeError(err: unknown): string {
+  if (err instanceof CommandError && err.hint) return `${err.message}. ${err.hint}`;
+  return err instanceof Error ? err.message : String(err);
+}
+
+/** Invoke a Rust command, rethrowing its error as a `CommandError`. */
+export async function invokeCommand<T = void>(
+  cmd: string,
+  args?: Record<string, unknown>
+): Promise<T> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  try {
+    return await invoke<T>(cmd, args);
+  } catch (err) {
+    throw toCommandError(err);
+  }
 }
 
 /**
@@ -24,3 +77,192 @@ export async function pickDirectory(title?: string): Promise<string | null> {
   });
   return typeof selected === "string" ? selected : null;
 }
+
+export type Sandbox = "none" | "flatpak" | "snap";
+
+/**
+ * The Linux sandbox the app runs in. Sandboxed apps can only reach folders
+ * the user picked through the file dialog, not typed paths.
+ */
+export async function getSandbox(): Promise<Sandbox> {
+  if (!isTauri()) return "none";
+  return invokeCommand<Sandbox>("get_sandbox");
+}
+
+/**
+ * Open a native file picker for a zipped or tarred vault export.
+ * Returns the selected path, or null if cancelled or not in Tauri.
+ */
+export async function pickArchive(title?: string): Promise<string | null> {
+  if (!isTauri()) return null;
+  return pickFile(title || "Select Archive", [{ name: "Archives", extensions: ["zip", "gz", "tgz"] }]);
+}
+
+interface FileFilter {
+  name: string;
+  extensions: string[];
+}
+
+/**
+ * File dialogs for paths handed back to Rust commands. The shell only
+ * accepts paths the user picked through these (or dropped on the window).
+ */
+function pickFile(title: string, filters: FileFilter[]): Promise<string | null> {
+  return invokeCommand<string | null>("pick_file", { title, filters });
+}
+
+function pickSaveFile(title: string, defaultName: string, filters: FileFilter[]): Promise<string | null> {
+  return invokeCommand<string | null>("pick_save_file", { title, defaultName, filters });
+}
+
+export interface ArchiveImport {
+  path: string;
+  files: number;
+  markdown_files: number;
+}
+
+/**
+ * Extract an archive into a staging directory via the Rust shell.
+ * The returned path can be passed straight to `importVault`. `onProgress`
+ * also gets the task id, for `cancelTask`.
+ */
+export async function importArchive(
+  path: string,
+  onProgress?: (fraction: number, taskId: number) => void
+): Promise<ArchiveImport> {
+  const { listen } = await import("@tauri-apps/api/event");
+  const unlisten = await listen<TaskInfo[]>("tasks-changed", (event) => {
+    const task = event.payload.find((t) => t.kind === "import_archive");
+    if (task) onProgress?.(task.total > 0 ? task.done / task.total : 0, task.id);
+  });
+  try {
+    return await invokeCommand<ArchiveImport>("import_archive", { path });
+  } finally {
+    unlisten();
+  }
+}
+
+/** Free a temporary directory returned by the Rust shell (e.g. `importArchive`). */
+export async function releaseWorkspaceDir(path: string): Promise<void> {
+  if (!isTauri()) return;
+  await invokeCommand("release_workspace_dir", { path });
+}
+
+export interface BackupInfo {
+  path: string;
+  files: number;
+  bytes: number;
+}
+
+/**
+ * Ask for a destination and write a backup of settings, the knowledge graph,
+ * and app data. Returns null if cancelled.
+ */
+export async function createBackup(): Promise<BackupInfo | null> {
+  if (!isTauri()) return null;
+  const date = new Date().toISOString().slice(0, 10);
+  const path = await pickSaveFile("Save Backup", `brainshape-backup-${date}.zip`, [
+    { name: "Brainshape Backup", extensions: ["zip"] },
+  ]);
+  if (!path) return null;
+  return invokeCommand<BackupInfo>("create_backup", { path });
+}
+
+/**
+ * Pick a backup and stage it for restore on the next launch. Existing data is
+ * moved aside rather than deleted. Returns the number of staged files, or
+ * null if cancelled.
+ */
+export async function restoreBackup(): Promise<number | null> {
+  if (!isTauri()) return null;
+  const { ask } = await import("@tauri-apps/plugin-dialog");
+  const path = await pickFile("Select Backup to Restore", [
+    { name: "Brainshape Backup", extensions: ["zip"] },
+  ]);
+  if (!path) return null;
+  const confirmed = await ask(
+    "Settings and the knowledge graph will be replaced with the backup the next time Brainshape starts. Your current data is kept alongside it.",
+    { title: "Restore Backup", kind: "warning" }
+  );
+  if (!confirmed) return null;
+  return invokeCommand<number>("restore_backup", { path, strategy: "replace" });
+}
+
+/**
+ * Save a copy of the file-access audit log (JSON lines). Returns the number
+ * of bytes written, or null if cancelled.
+ */
+export async function exportAuditLog(): Promise<number | null> {
+  if (!isTauri()) return null;
+  const path = await pickSaveFile("Export Access Log", "brainshape-access-log.jsonl", [
+    { name: "JSON Lines", extensions: ["jsonl"] },
+  ]);
+  if (!path) return null;
+  return invokeCommand<number>("export_audit_log", { path });
+}
+
+/** Name of the platform file manager, for menu labels. */
+export function fileManagerName(): string {
+  const platform = navigator.userAgent;
+  if (platform.includes("Mac")) return "Finder";
+  if (platform.includes("Windows")) return "Explorer";
+  return "File Manager";
+}
+
+/** Open a note or folder (relative to the notes folder) in the default application. */
+export async function openNoteExternally(path: string): Promise<void> {
+  if (!isTauri()) return;
+  await invokeCommand("open_note_externally", { path });
+}
+
+/** Show a note or folder (relative to the notes folder) in the platform file manager. */
+export async function revealNote(path: string): Promise<void> {
+  if (!isTauri()) return;
+  await invokeCommand("reveal_note", { path });
+}
+
+/**
+ * Open a GitHub issue prefilled with `message` and the app version and OS.
+ * Nothing is posted until the user submits the form in the browser.
+ */
+export async function reportProblem(message: string): Promise<void> {
+  await invokeCommand("report_problem", { message });
+}
+
+export type ShortcutAction = "toggle_window" | "new_note" | "search";
+
+export interface ShortcutStatus {
+  action: ShortcutAction;
+  accelerator: string | null;
+  registered: boolean;
+  error: string | null;
+}
+
+/** Global shortcut bindings and whether the OS accepted each one. */
+export async function getShortcuts(): Promise<ShortcutStatus[]> {
+  if (!isTauri()) return [];
+  return invokeCommand<ShortcutStatus[]>("get_shortcuts");
+}
+
+/**
+ * Bind a global shortcut (e.g. "CommandOrControl+Alt+N"), or disable it with null.
+ * Rejects invalid or duplicate accelerators.
+ */
+export async function setShortcut(action: ShortcutAction, accelerator: string | null): Promise<ShortcutStatus[]> {
+  return invokeCommand<ShortcutStatus[]>("set_shortcut", { action, accelerator });
+}
+
+/**
+ * Listen for the backend becoming ready, and for an external backend (dev
+ * server or `--backend-url`) going away or coming back. Returns an unlisten
+ * function.
+ */
+export async function listenBackendConnection(
+  onChange: (connected: boolean) => void
+): Promise<() => void> {
+  if (!isTauri()) return () => {};
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<{ connected: boolean }>("backend-connection", (event) =>
+    onChange(event.payload.connected)
+  );
+}
@@ -1,5 +1,6 @@
-import { useCallback, useRef, useState } from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
 import { getBaseUrl, initSession } from "./api";
+import { listenBackendConnection } from "./tauri";
 
 export type MessagePart =
   | { type: "text"; content: string }
@@ -18,6 +19,15 @@ export function useAgentStream() {
   const sessionInitRef = useRef<Promise<string> | null>(null);
   const abortRef = useRef<AbortController | null>(null);
 
+  // A restarted backend has forgotten our session; start a new one on the
+  // next message.
+  useEffect(() => {
+    const unlisten = listenBackendConnection((up) => {
+      if (up) sessionIdRef.current = null;
+    });
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
   const ensureSession = useCallback(async () => {
     if (sessionIdRef.current) return sessionIdRef.current;
     // Guard against concurrent init calls — reuse in-flight promise
@@ -2,11 +2,13 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
 import { ErrorBoundary } from "./components/ErrorBoundary";
+import { StartupError } from "./components/StartupError";
+import { isStartupErrorView } from "./lib/startup";
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <ErrorBoundary>
-      <App />
+      {isStartupErrorView() ? <StartupError /> : <App />}
     </ErrorBoundary>
   </React.StrictMode>,
 );